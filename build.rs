@@ -0,0 +1,302 @@
+//! The build script validates the built-in catalog of categories and invocables in src/wsl/inv/catalog.toml
+//! and writes it to wink.json in OUT_DIR in the form that wink -e exports,
+//! which InvocableCategoryList::built_in() embeds in the binary.
+//! Each category names a cargo feature, and the build excludes the categories of disabled features,
+//! listing them in excluded.rs in OUT_DIR for help to report.
+//! The build fails if the catalog contains command codes or aliases that differ only in case, unknown tokens, invalid launch modes or argument kinds,
+//! or categories without features declared in Cargo.toml.
+
+// the argument kinds, launch modes, and tokens of the catalog are those of the wink crate
+#[allow(dead_code)]
+#[path = "src/wsl/inv/argumentkind.rs"]
+mod argumentkind;
+
+#[allow(dead_code)]
+#[path = "src/wsl/inv/launchmode.rs"]
+mod launchmode;
+
+#[allow(dead_code)]
+#[path = "src/wsl/inv/tokens.rs"]
+mod tokens;
+
+use argumentkind::ArgumentKind;
+use launchmode::LaunchMode;
+use tokens::TOKENS;
+
+/// The path to the catalog, relative to the directory that contains Cargo.toml.
+const CATALOG_PATH: &str = "src/wsl/inv/catalog.toml";
+
+/// The fields of categories in the catalog.
+const CATEGORY_FIELDS: [&str; 3] = ["name", "feature", "invocables"];
+
+/// The fields of invocables in the catalog, in the order that wink exports them.
+const INVOCABLE_FIELDS: [&str; 9] = [
+    "command_code",
+    "aliases",
+    "description",
+    "command",
+    "mode",
+    "arguments",
+    "argument_kind",
+    "tags",
+    "keywords",
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={0}", CATALOG_PATH);
+    println!("cargo:rerun-if-changed=src/wsl/inv/argumentkind.rs");
+    println!("cargo:rerun-if-changed=src/wsl/inv/launchmode.rs");
+    println!("cargo:rerun-if-changed=src/wsl/inv/tokens.rs");
+
+    let data = std::fs::read_to_string(CATALOG_PATH).unwrap_or_else(|e| fail(&[format!("cannot read: {0}", e)]));
+    let catalog: serde_json::Value = toml::from_str(&data).unwrap_or_else(|e| fail(&[e.to_string()]));
+
+    let features = declared_features().unwrap_or_else(|e| fail(&[e]));
+
+    match read_catalog(&catalog, &features) {
+        Ok((exported, excluded)) => {
+            let dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+            std::fs::write(dir.join("wink.json"), serde_json::to_string_pretty(&exported).unwrap()).unwrap();
+            std::fs::write(dir.join("excluded.rs"), excluded_source(&excluded)).unwrap();
+        }
+        Err(errors) => fail(&errors),
+    }
+}
+
+/// A category that the build excludes: its name, its feature, and its command codes.
+type Excluded = (String, String, Vec<String>);
+
+/// Return the names of the features declared in Cargo.toml.
+fn declared_features() -> Result<Vec<String>, String> {
+    let data = std::fs::read_to_string("Cargo.toml").map_err(|e| format!("cannot read Cargo.toml: {0}", e))?;
+    let manifest: serde_json::Value = toml::from_str(&data).map_err(|e| format!("Cargo.toml: {0}", e))?;
+    Ok(object(&manifest["features"]).keys().cloned().collect())
+}
+
+/// Return true if cargo enabled the given feature for this build.
+fn is_enabled(feature: &str) -> bool {
+    std::env::var_os(format!("CARGO_FEATURE_{0}", feature.to_uppercase().replace('-', "_"))).is_some()
+}
+
+/// Return Rust source for the excluded categories, as in &[("Shutdown", "cat-shutdown", &["boot"])].
+fn excluded_source(excluded: &[Excluded]) -> String {
+    let mut source = String::from("&[\n");
+
+    for (name, feature, codes) in excluded {
+        source.push_str(&format!("    ({0:?}, {1:?}, &{2:?}),\n", name, feature, codes));
+    }
+
+    source.push(']');
+    source
+}
+
+/// Write the given problems with the catalog to stderr and fail the build.
+fn fail(errors: &[String]) -> ! {
+    for error in errors {
+        eprintln!("{0}: {1}", CATALOG_PATH, error);
+    }
+
+    std::process::exit(1);
+}
+
+/// Return the categories of the enabled features of the catalog in the form that wink exports
+/// and the categories of the other features, or all of the problems with the catalog.
+fn read_catalog(
+    catalog: &serde_json::Value,
+    features: &[String],
+) -> Result<(serde_json::Value, Vec<Excluded>), Vec<String>> {
+    let mut errors: Vec<String> = vec![];
+    let mut defined: Vec<(String, String)> = vec![];
+    let mut names: Vec<String> = vec![];
+    let mut used: Vec<String> = vec![];
+    let mut categories: Vec<serde_json::Value> = vec![];
+    let mut excluded: Vec<Excluded> = vec![];
+
+    for key in object(catalog).keys() {
+        if key != "format_version" && key != "categories" {
+            errors.push(format!("unknown field {0}", key));
+        }
+    }
+
+    for category in catalog["categories"].as_array().cloned().unwrap_or_default() {
+        let name = category["name"].as_str().unwrap_or_default().to_string();
+
+        let feature = category["feature"].as_str().unwrap_or_default().to_string();
+
+        if name.is_empty() {
+            errors.push("category without a name".to_string());
+        } else if names.contains(&name) {
+            errors.push(format!("category {0} defined more than once", name));
+        }
+
+        if !features.contains(&feature) {
+            errors.push(format!("category {0} does not name a feature in Cargo.toml", name));
+        } else if used.contains(&feature) {
+            errors.push(format!("feature {0} of category {1} names another category", feature, name));
+        }
+
+        for key in object(&category).keys() {
+            if !CATEGORY_FIELDS.contains(&key.as_str()) {
+                errors.push(format!("unknown field {0} for category {1}", key, name));
+            }
+        }
+
+        names.push(name.clone());
+        used.push(feature.clone());
+
+        // categories list invocables or map command codes to invocables
+        let entries: Vec<(Option<String>, serde_json::Value)> = match &category["invocables"] {
+            serde_json::Value::Array(list) => list.iter().map(|i| (None, i.clone())).collect(),
+            serde_json::Value::Object(map) => map.iter().map(|(k, i)| (Some(k.clone()), i.clone())).collect(),
+            _ => {
+                errors.push(format!("category {0} has no invocables", name));
+                vec![]
+            }
+        };
+
+        let mut invocables: Vec<serde_json::Value> = vec![];
+
+        for (code, entry) in entries {
+            match read_invocable(code, entry) {
+                Ok(invocable) => {
+                    for code in codes(&invocable) {
+                        match defined.iter().find(|(c, _n)| c.eq_ignore_ascii_case(&code)) {
+                            Some((_c, other)) => errors.push(format!(
+                                "command code {0} defined in both {1} and {2}",
+                                code, other, name
+                            )),
+                            None => defined.push((code, name.clone())),
+                        }
+                    }
+
+                    invocables.push(invocable);
+                }
+                Err(error) => errors.push(format!("{0} in category {1}", error, name)),
+            }
+        }
+
+        if is_enabled(&feature) {
+            categories.push(serde_json::json!({ "name": name, "invocables": invocables }));
+        } else {
+            let codes = invocables.iter().flat_map(codes).collect();
+            excluded.push((name, feature, codes));
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let exported = serde_json::json!({
+        "format_version": catalog["format_version"],
+        "categories": categories,
+    });
+
+    Ok((exported, excluded))
+}
+
+/// Return the given invocable from the catalog in the form that wink exports, or its problem.
+/// Map entries take the command code from their key, and a string is shorthand for the command.
+fn read_invocable(code: Option<String>, entry: serde_json::Value) -> Result<serde_json::Value, String> {
+    let mut fields = match entry {
+        serde_json::Value::String(command) => serde_json::json!({ "command": command }),
+        serde_json::Value::Object(_) => entry,
+        _ => return Err(format!("invocable {0} is not a command or a table", code.unwrap_or_default())),
+    };
+
+    if let Some(code) = code {
+        fields["command_code"] = serde_json::Value::String(code);
+    }
+
+    let code = fields["command_code"].as_str().unwrap_or_default().to_string();
+
+    if code.is_empty() {
+        return Err("invocable without a command code".to_string());
+    }
+
+    for key in object(&fields).keys() {
+        if !INVOCABLE_FIELDS.contains(&key.as_str()) {
+            return Err(format!("unknown field {0} for command code {1}", key, code));
+        }
+    }
+
+    let mode: LaunchMode = match fields.get("mode") {
+        Some(mode) => serde_json::from_value(mode.clone()).map_err(|e| format!("{0} for command code {1}", e, code))?,
+        None => LaunchMode::Direct,
+    };
+
+    let argument_kind: ArgumentKind = match fields.get("argument_kind") {
+        Some(kind) => serde_json::from_value(kind.clone()).map_err(|e| format!("{0} for command code {1}", e, code))?,
+        None => ArgumentKind::None,
+    };
+
+    let command = fields["command"].as_str().unwrap_or_default();
+
+    if mode.is_direct() && command.is_empty() {
+        return Err(format!("command code {0} has no command to invoke directly", code));
+    }
+
+    // bash.exe commands can refer to shell variables, such as $PWD
+    if mode != LaunchMode::Bash {
+        if let Some(token) = unknown_token(command) {
+            return Err(format!("unknown token {0} in the command for command code {1}", token, code));
+        }
+    }
+
+    // export the fields in order, omitting those with default values, and the mode in its exported form
+    let mut exported = serde_json::Map::new();
+
+    for key in INVOCABLE_FIELDS.iter() {
+        let value = match *key {
+            "mode" if mode.is_direct() => serde_json::Value::Null,
+            "mode" => serde_json::to_value(mode).unwrap(),
+            "argument_kind" if argument_kind.is_none() => serde_json::Value::Null,
+            "argument_kind" => serde_json::to_value(&argument_kind).unwrap(),
+            _ => fields[*key].clone(),
+        };
+
+        let present = match (*key, &value) {
+            (_, serde_json::Value::Null) => continue,
+            ("aliases" | "arguments" | "tags", serde_json::Value::Array(a)) if a.iter().all(|v| v.is_string()) => !a.is_empty(),
+            ("aliases" | "arguments" | "tags", _) => {
+                return Err(format!("{0} for command code {1} are not a list of strings", key, code))
+            }
+            ("mode" | "argument_kind", _) => true,
+            (_, serde_json::Value::String(s)) => !s.is_empty(),
+            (_, _) => return Err(format!("{0} for command code {1} is not a string", key, code)),
+        };
+
+        if present {
+            exported.insert(key.to_string(), value);
+        }
+    }
+
+    Ok(serde_json::Value::Object(exported))
+}
+
+/// Return the command code and then the aliases of the given invocable in the form that wink exports.
+fn codes(invocable: &serde_json::Value) -> Vec<String> {
+    std::iter::once(&invocable["command_code"])
+        .chain(invocable["aliases"].as_array().into_iter().flatten())
+        .filter_map(|c| c.as_str())
+        .map(|c| c.to_string())
+        .collect()
+}
+
+/// Return the first word that follows a $ in the given command but does not start with a token, if any.
+/// Tokens can precede other characters, as in $syslivebginfo64.exe.
+fn unknown_token(command: &str) -> Option<String> {
+    command
+        .split('$')
+        .skip(1)
+        .map(|rest| rest.split(|c: char| !c.is_ascii_alphanumeric()).next().unwrap_or_default())
+        .filter(|word| !word.is_empty())
+        .find(|word| !TOKENS.iter().any(|(token, _description)| word.starts_with(&token[1..])))
+        .map(|word| format!("${0}", word))
+}
+
+/// Return the fields of the given table, or no fields if it is not a table.
+fn object(value: &serde_json::Value) -> serde_json::Map<String, serde_json::Value> {
+    value.as_object().cloned().unwrap_or_default()
+}
@@ -0,0 +1,6 @@
+//! The wink.completions module generates scripts that complete wink command codes and options
+//! in bash, zsh, fish, and PowerShell.
+
+pub mod completecommand; // /src/completions/completecommand.rs defines the hidden wink __complete command
+pub mod completionscommand; // /src/completions/completionscommand.rs defines the wink completions command
+pub mod shell; // /src/completions/shell.rs defines completions::shell::Shell
@@ -0,0 +1,249 @@
+//! The hidden wink __complete command writes the values that complete the last word of a partial command line
+//! to stdout, one per line with any description after a tab, for the scripts that wink completions generates.
+//! The scripts pass the words after the command name, including the partial word, which can be empty,
+//! such as wink __complete -v oa "C:/Users/".
+
+use crate::completions::completionscommand::{codes, options, COMPLETIONS_COMMAND_CODE}; // /src/completions/completionscommand.rs
+use crate::completions::shell::SHELL_NAMES; // /src/completions/shell.rs
+use crate::winkconfig::WinkConfig; // /src/winkconfig.rs
+use crate::wsl::inv::argumentkind::ArgumentKind; // /src/wsl/inv/argumentkind.rs
+use crate::wsl::inv::invocablecategorylist::InvocableCategoryList; // /src/wsl/inv/invocablecategorylist.rs
+
+/// The command code that selects the wink __complete command, which help and completion do not list.
+pub const COMPLETE_COMMAND_CODE: &str = "__complete";
+
+/// The URL schemes that complete the arguments of invocables that accept URLs.
+const URL_SCHEMES: [&str; 2] = ["https://", "http://"];
+
+/// Write the values that complete the last of the words after __complete to stdout
+/// and return the exit code for the process.
+pub fn run(config: &WinkConfig, category_list: &InvocableCategoryList) -> i32 {
+    for (value, description) in complete(category_list, &config.cmd_args) {
+        if description.is_empty() {
+            println!("{}", value);
+        } else {
+            println!("{0}\t{1}", value, description);
+        }
+    }
+
+    0
+}
+
+/// Return the values that complete the last of the given words, with their descriptions:
+/// options or command codes before the command code, then the arguments of the command code,
+/// which depend on the argument kind of its invocable. No values lets the shell complete as it would by default.
+pub fn complete(category_list: &InvocableCategoryList, words: &[String]) -> Vec<(String, String)> {
+    let (current, previous) = match words.split_last() {
+        Some((current, previous)) => (current.as_str(), previous),
+        None => ("", words),
+    };
+
+    // options come before the command code, and arguments for the command follow it
+    let mut previous = previous.iter().skip_while(|word| word.starts_with('-'));
+
+    let code = match previous.next() {
+        Some(code) => code.as_str(),
+        None if current.starts_with('-') => return starting_with(options(), current),
+        None => return starting_with(codes(category_list), current),
+    };
+
+    let arguments = previous.count();
+
+    match code {
+        COMPLETIONS_COMMAND_CODE if arguments == 0 => starting_with(values(&SHELL_NAMES, "Shell"), current),
+        crate::docs::docscommand::DOCS_COMMAND_CODE if arguments == 0 => {
+            starting_with(values(&crate::docs::docformat::DOC_FORMAT_NAMES, "Documentation format"), current)
+        }
+        crate::config::configcommand::CONFIG_COMMAND_CODE if arguments == 0 => {
+            let mut actions = values(&crate::config::configcommand::EDIT_ACTIONS, "Change user configuration");
+            actions.push(("migrate".to_string(), "Upgrade configuration file format".to_string()));
+            starting_with(actions, current)
+        }
+        crate::doctor::doctorcommand::DOCTOR_COMMAND_CODE if current.starts_with('-') => {
+            starting_with(values(&["--json", "-v"], "Doctor option"), current)
+        }
+        crate::doctor::doctorcommand::DOCTOR_COMMAND_CODE => starting_with(codes(category_list), current),
+        _ => match category_list.find_invocable(code) {
+            Some(invocable) => arguments_of(&invocable.argument_kind, current),
+            None => vec![],
+        },
+    }
+}
+
+/// Return the values of the given argument kind that start with the partial argument.
+fn arguments_of(kind: &ArgumentKind, current: &str) -> Vec<(String, String)> {
+    match kind {
+        ArgumentKind::None => vec![],
+        ArgumentKind::File => paths(current, false),
+        ArgumentKind::Directory => paths(current, true),
+        ArgumentKind::Url => starting_with(values(&URL_SCHEMES, "URL"), current),
+        ArgumentKind::Distro => starting_with(distros(), current),
+        ArgumentKind::Choice(choices) => {
+            let choices: Vec<&str> = choices.iter().map(|c| c.as_str()).collect();
+            starting_with(values(&choices, ""), current)
+        }
+    }
+}
+
+/// Return the given values with the same description.
+fn values(values: &[&str], description: &str) -> Vec<(String, String)> {
+    values.iter().map(|v| (v.to_string(), description.to_string())).collect()
+}
+
+/// Return the candidates that start with the partial word, ignoring case.
+fn starting_with(candidates: Vec<(String, String)>, current: &str) -> Vec<(String, String)> {
+    let current = current.to_lowercase();
+    candidates
+        .into_iter()
+        .filter(|(value, _description)| value.to_lowercase().starts_with(&current))
+        .collect()
+}
+
+/// Return the paths of the files and directories, or only the directories, that start with the partial path,
+/// in the Linux form, such as /mnt/c/Users/, or the Windows form, such as C:\Users\ or C:/Users/,
+/// that the user started typing. Directories end with a separator so that completion can continue into them.
+/// Names that start with a dot match only partial names that start with a dot.
+pub fn paths(current: &str, directories_only: bool) -> Vec<(String, String)> {
+    let chars: Vec<char> = current.chars().collect();
+    let drive = chars.len() >= 2 && chars[0].is_ascii_alphabetic() && chars[1] == ':';
+    let windows = drive || current.contains('\\');
+    let separator = if current.contains('\\') { '\\' } else { '/' };
+
+    // a drive alone, such as C:, completes the root directory of the drive
+    let (dir, name) = match current.rfind(['/', '\\']) {
+        Some(i) => (current[..=i].to_string(), &current[i + 1..]),
+        None if drive && chars.len() == 2 => (format!("{0}{1}", current, separator), ""),
+        None => (String::new(), current),
+    };
+
+    let read_dir = if dir.is_empty() {
+        ".".to_string()
+    } else if windows && !cfg!(target_os = "windows") {
+        crate::wsl::wsl_path_or_self(&dir, true)
+    } else {
+        dir.clone()
+    };
+
+    let entries = match std::fs::read_dir(read_dir) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+
+    let mut result: Vec<(String, String)> = vec![];
+
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let is_dir = entry.path().is_dir();
+
+        // Windows file names ignore case
+        let matches = if windows {
+            file_name.to_lowercase().starts_with(&name.to_lowercase())
+        } else {
+            file_name.starts_with(name)
+        };
+
+        if !matches || (file_name.starts_with('.') && !name.starts_with('.')) || (directories_only && !is_dir) {
+            continue;
+        }
+
+        if is_dir {
+            result.push((format!("{0}{1}{2}", dir, file_name, separator), String::new()));
+        } else {
+            result.push((format!("{0}{1}", dir, file_name), String::new()));
+        }
+    }
+
+    result.sort();
+    result
+}
+
+/// Return the names of the Windows Subsystem for Linux distributions from wsl.exe --list --quiet,
+/// or the current distribution if wsl.exe is not available.
+fn distros() -> Vec<(String, String)> {
+    let current = std::env::var("WSL_DISTRO_NAME").unwrap_or_default();
+    let mut names: Vec<String> = vec![];
+
+    if let Ok(output) = std::process::Command::new("wsl.exe").args(["--list", "--quiet"]).output() {
+        // wsl.exe writes UTF-16
+        let text = if output.stdout.contains(&0) {
+            let units: Vec<u16> = output.stdout.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+            String::from_utf16_lossy(&units)
+        } else {
+            String::from_utf8_lossy(&output.stdout).to_string()
+        };
+
+        names.extend(text.lines().map(|l| l.trim().trim_start_matches('\u{feff}').to_string()).filter(|l| !l.is_empty()));
+    }
+
+    if names.is_empty() && !current.is_empty() {
+        names.push(current.clone());
+    }
+
+    names
+        .into_iter()
+        .map(|name| {
+            let description = if name == current {
+                "WSL distribution (current)"
+            } else {
+                "WSL distribution"
+            };
+            (name, description.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::completions::completecommand::{complete, paths};
+    use crate::wsl::inv::argumentkind::ArgumentKind;
+    use crate::wsl::inv::invocable::Invocable;
+    use crate::wsl::inv::invocablecategory::InvocableCategory;
+    use crate::wsl::inv::invocablecategorylist::InvocableCategoryList;
+
+    fn words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn values(candidates: Vec<(String, String)>) -> Vec<String> {
+        candidates.into_iter().map(|(value, _description)| value).collect()
+    }
+
+    #[test]
+    #[cfg(all(feature = "cat-office", feature = "cat-applications"))]
+    fn it_completes_codes_options_and_commands() {
+        let category_list = InvocableCategoryList::built_in();
+        assert!(values(complete(&category_list, &words(&["-v", "exc"]))).contains(&"excel".to_string()));
+        assert_eq!(values(complete(&category_list, &words(&["--f"]))), vec!["--format=json", "--format=toml", "--format=yaml"]);
+        assert_eq!(values(complete(&category_list, &words(&["completions", "p"]))), vec!["powershell"]);
+        assert_eq!(values(complete(&category_list, &words(&["docs", "m"]))), vec!["markdown", "man"]);
+        assert_eq!(values(complete(&category_list, &words(&["config", "m"]))), vec!["move", "migrate"]);
+        assert_eq!(values(complete(&category_list, &words(&["oa", "https:"]))), Vec::<String>::new());
+        assert_eq!(values(complete(&category_list, &words(&["firefox", ""]))), vec!["https://", "http://"]);
+
+        // invocables without an argument kind leave completion to the shell
+        assert!(complete(&category_list, &words(&["word", "src"])).is_empty());
+    }
+
+    #[test]
+    fn it_completes_choices() {
+        let mut category = InvocableCategory::new("Mine");
+        let mut invocable = Invocable::bin("power", "powercfg.exe", "Power scheme");
+        invocable.argument_kind = ArgumentKind::Choice(words(&["balanced", "high", "saver"]));
+        category.add(invocable);
+        let category_list = InvocableCategoryList { categories: vec![category], ..InvocableCategoryList::built_in() };
+        assert_eq!(values(complete(&category_list, &words(&["-d", "power", "/s", "h"]))), vec!["high"]);
+
+        let category: InvocableCategory = serde_json::from_str(
+            r#"{ "name": "Mine", "invocables": { "power": { "command": "powercfg.exe", "argument_kind": { "choice": [ "balanced" ] } } } }"#,
+        )
+        .unwrap();
+        assert_eq!(category.invocables[0].argument_kind, ArgumentKind::Choice(words(&["balanced"])));
+    }
+
+    #[test]
+    fn it_completes_paths() {
+        assert_eq!(values(paths("src/wsl/inv/argu", false)), vec!["src/wsl/inv/argumentkind.rs"]);
+        assert_eq!(values(paths("src/co", true)), vec!["src/completions/", "src/config/"]);
+        assert!(paths("src/nonexistent/", false).is_empty());
+    }
+}
@@ -0,0 +1,127 @@
+//! The wink completions command writes a script that completes wink command codes and options to stdout,
+//! such as wink completions bash > ~/.local/share/bash-completion/completions/wink.
+
+use crate::completions::shell::{Shell, SHELL_NAMES}; // /src/completions/shell.rs
+use crate::winkconfig::WinkConfig; // /src/winkconfig.rs
+use crate::wsl::inv::invocablecategorylist::InvocableCategoryList; // /src/wsl/inv/invocablecategorylist.rs
+
+/// The command code that selects the wink completions command.
+pub const COMPLETIONS_COMMAND_CODE: &str = "completions";
+
+/// Write the completion script for the shell named after completions to stdout
+/// and return the exit code for the process.
+pub fn run(config: &WinkConfig, category_list: &InvocableCategoryList) -> i32 {
+    let shell = match config.cmd_args.as_slice() {
+        [name] => Shell::from_name(name),
+        _ => None,
+    };
+
+    match shell {
+        Some(shell) => {
+            // complete wink.exe as well as wink, whichever the script was generated with
+            let name = config.cmd_name.trim_end_matches(".exe");
+            print!("{}", shell.script(name, &codes(category_list), &options()));
+            0
+        }
+        None => {
+            eprintln!("{0} completions {1}", config.cmd_name, SHELL_NAMES.join("|"));
+            1
+        }
+    }
+}
+
+/// Return the commands that wink handles itself and the command codes and aliases of the merged list,
+/// including those from configuration files, with their descriptions or otherwise their categories.
+pub fn codes(category_list: &InvocableCategoryList) -> Vec<(String, String)> {
+    let mut codes: Vec<(String, String)> = vec![
+        (
+            crate::config::configcommand::CONFIG_COMMAND_CODE.to_string(),
+            "Change user configuration".to_string(),
+        ),
+        (
+            crate::doctor::doctorcommand::DOCTOR_COMMAND_CODE.to_string(),
+            "Check commands and configuration".to_string(),
+        ),
+        (COMPLETIONS_COMMAND_CODE.to_string(), "Write a shell completion script".to_string()),
+        (
+            crate::docs::docscommand::DOCS_COMMAND_CODE.to_string(),
+            "Write documentation for command codes".to_string(),
+        ),
+        (crate::SEARCH_COMMAND_CODE.to_string(), "Find command codes by code, description, tag, or keyword".to_string()),
+    ];
+
+    for category in category_list.categories.iter() {
+        for invocable in category.invocables.iter() {
+            let description = if invocable.description.is_empty() {
+                &category.name
+            } else {
+                &invocable.description
+            };
+
+            for code in invocable.codes() {
+                codes.push((code.to_string(), description.to_string()));
+            }
+        }
+    }
+
+    codes
+}
+
+/// Return the options that WinkConfig::new parses with their descriptions,
+/// with an option for each value of options such as --format=json|toml|yaml.
+pub fn options() -> Vec<(String, String)> {
+    let mut options: Vec<(String, String)> = vec![];
+
+    for (option, description) in crate::winkconfig::OPTIONS.iter() {
+        match option.split_once('=') {
+            Some((name, values)) => {
+                for value in values.split('|') {
+                    options.push((format!("{0}={1}", name, value), description.to_string()));
+                }
+            }
+            None => options.push((option.to_string(), description.to_string())),
+        }
+    }
+
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(all(feature = "cat-office", feature = "cat-applications"))]
+    fn it_completes_codes_and_options() {
+        let category_list = crate::wsl::inv::invocablecategorylist::InvocableCategoryList::built_in();
+        let codes = crate::completions::completionscommand::codes(&category_list);
+        assert!(codes.contains(&("word".to_string(), "Microsoft Word".to_string())));
+        assert!(codes.iter().any(|(code, _description)| code == "vsc"));
+        assert!(codes.iter().any(|(code, _description)| code == "doctor"));
+        assert!(codes.iter().all(|(_code, description)| !description.is_empty()));
+
+        let options: Vec<String> = crate::completions::completionscommand::options()
+            .into_iter()
+            .map(|(option, _description)| option)
+            .collect();
+        assert_eq!(
+            options,
+            vec![
+                "-a",
+                "--color=auto",
+                "--color=always",
+                "--color=never",
+                "-d",
+                "-e",
+                "--format=json",
+                "--format=toml",
+                "--format=yaml",
+                "-h",
+                "-p",
+                "-s",
+                "-v"
+            ]
+        );
+
+        let options = crate::completions::completionscommand::options();
+        assert!(options.contains(&("-e".to_string(), "export (configuration JSON)".to_string())));
+    }
+}
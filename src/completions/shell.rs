@@ -0,0 +1,320 @@
+//! A Shell identifies a shell that wink can generate a completion script for, bash, zsh, fish, or PowerShell,
+//! and writes the script that completes the given command codes and options with their descriptions.
+
+/// The names of the shells for the wink completions command.
+pub const SHELL_NAMES: [&str; 4] = ["bash", "zsh", "fish", "powershell"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+}
+
+impl Shell {
+    /// Return the shell with the given name, ignoring case, such as powershell or pwsh, if any.
+    pub fn from_name(name: &str) -> Option<Shell> {
+        match name.to_lowercase().as_str() {
+            "bash" => Some(Shell::Bash),
+            "zsh" => Some(Shell::Zsh),
+            "fish" => Some(Shell::Fish),
+            "powershell" | "pwsh" => Some(Shell::PowerShell),
+            _ => None,
+        }
+    }
+
+    /// Return the completion script for the command with the given name, such as wink,
+    /// which completes the options before the command code, then the command code, and then calls
+    /// wink __complete for the arguments, completing file names if it returns nothing.
+    /// The codes and options are pairs of the text to complete and its description.
+    pub fn script(&self, name: &str, codes: &[(String, String)], options: &[(String, String)]) -> String {
+        match self {
+            Shell::Bash => bash(name, codes, options),
+            Shell::Zsh => zsh(name, codes, options),
+            Shell::Fish => fish(name, codes, options),
+            Shell::PowerShell => powershell(name, codes, options),
+        }
+    }
+}
+
+/// Return the text quoted for the single quotes of a POSIX shell.
+fn posix_quote(text: &str) -> String {
+    format!("'{0}'", text.replace('\'', r"'\''"))
+}
+
+/// Return the bash completion script, which completes the codes and options without descriptions,
+/// which bash cannot show.
+fn bash(name: &str, codes: &[(String, String)], options: &[(String, String)]) -> String {
+    let words = |list: &[(String, String)]| list.iter().map(|(word, _d)| word.as_str()).collect::<Vec<&str>>().join(" ");
+    let function = format!("_{0}", name.replace(|c: char| !c.is_ascii_alphanumeric(), "_"));
+
+    format!(
+        r#"# bash completion for {0}, generated by {0} completions bash
+{1}() {{
+    # split the line before the cursor at whitespace, because COMP_WORDS also splits C:/ and https:// at colons
+    local line="${{COMP_LINE:0:COMP_POINT}}"
+    local -a words
+    read -ra words <<< "$line"
+
+    if [[ -z $line || $line == *[[:space:]] ]]; then
+        words+=("")
+    fi
+
+    local cword=$((${{#words[@]}} - 1))
+    local cur="${{words[cword]}}"
+    local i=1
+
+    # options come before the command code, and arguments for the command follow it
+    while [[ $i -lt $cword && ${{words[i]}} == -* ]]; do
+        ((i++))
+    done
+
+    if [[ $i -lt $cword ]]; then
+        local IFS=$'\n'
+        COMPREPLY=($({0} __complete "${{words[@]:1:cword}}" 2>/dev/null | cut -f1))
+
+        # directories and URL schemes end with a separator so that completion can continue after them
+        if [[ ${{#COMPREPLY[@]}} -eq 1 && ${{COMPREPLY[0]}} == *[/\\] ]]; then
+            compopt -o nospace
+        fi
+    elif [[ $cur == -* ]]; then
+        COMPREPLY=($(compgen -W {2} -- "$cur"))
+    else
+        COMPREPLY=($(compgen -W {3} -- "$cur"))
+    fi
+
+    # bash replaces only the text after the last colon of the word, so remove the text before it
+    if [[ $cur == *:* && $COMP_WORDBREAKS == *:* ]]; then
+        local prefix="${{cur%"${{cur##*:}}"}}"
+        COMPREPLY=("${{COMPREPLY[@]#"$prefix"}}")
+    fi
+}}
+
+complete -o default -F {1} {0} {0}.exe
+"#,
+        name,
+        function,
+        posix_quote(&words(options)),
+        posix_quote(&words(codes))
+    )
+}
+
+/// Return the zsh completion script, which escapes colons in codes for _describe.
+fn zsh(name: &str, codes: &[(String, String)], options: &[(String, String)]) -> String {
+    let describe = |list: &[(String, String)]| {
+        list.iter()
+            .map(|(word, description)| {
+                format!("        {0}", posix_quote(&format!("{0}:{1}", word.replace(':', r"\:"), description)))
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+    let function = format!("_{0}", name.replace(|c: char| !c.is_ascii_alphanumeric(), "_"));
+
+    format!(
+        r#"#compdef {0} {0}.exe
+# zsh completion for {0}, generated by {0} completions zsh
+
+{1}() {{
+    local -a codes options
+    local i
+
+    codes=(
+{2}
+    )
+    options=(
+{3}
+    )
+
+    # options come before the command code, and arguments for the command follow it
+    for ((i = 2; i < CURRENT; i++)); do
+        if [[ $words[i] != -* ]]; then
+            local -a values
+            values=(${{(f)"$({0} __complete "${{(@)words[2,CURRENT]}}" 2>/dev/null)"}})
+            values=("${{(@)values%%$'\t'*}}")
+
+            if (( ${{#values}} )); then
+                # directories and URL schemes end with a separator so that completion can continue after them
+                compadd -Q -S '' -- ${{(M)values:#*[/\\]}}
+                compadd -Q -- ${{values:#*[/\\]}}
+            else
+                _files
+            fi
+
+            return
+        fi
+    done
+
+    if [[ $PREFIX == -* ]]; then
+        _describe 'option' options
+    else
+        _describe 'command code' codes
+    fi
+}}
+
+compdef {1} {0} {0}.exe
+"#,
+        name,
+        function,
+        describe(codes),
+        describe(options)
+    )
+}
+
+/// Return the fish completion script, which completes the codes only before the first argument that is not an option,
+/// and which shows the descriptions that wink __complete writes after tabs, for both the command
+/// and the command with the .exe extension.
+fn fish(name: &str, codes: &[(String, String)], options: &[(String, String)]) -> String {
+    let quote = |text: &str| format!("'{0}'", text.replace('\\', r"\\").replace('\'', r"\'"));
+    let prefix = format!("__{0}", name.replace(|c: char| !c.is_ascii_alphanumeric(), "_"));
+    let mut script = format!(
+        r#"# fish completion for {0}, generated by {0} completions fish
+
+# options come before the command code, and arguments for the command follow it
+function {1}_needs_code
+    for token in (commandline -opc)[2..-1]
+        if not string match -q -- '-*' $token
+            return 1
+        end
+    end
+    return 0
+end
+
+function {1}_arguments
+    set -l values ({0} __complete (commandline -opc)[2..-1] (commandline -ct) 2>/dev/null)
+
+    if test (count $values) -gt 0
+        printf '%s\n' $values
+    else
+        __fish_complete_path (commandline -ct)
+    end
+end
+
+"#,
+        name, prefix
+    );
+
+    for command in [name.to_string(), format!("{}.exe", name)] {
+        if !script.ends_with("\n\n") {
+            script.push('\n');
+        }
+
+        script.push_str(&format!(
+            "complete -c {0} -f\ncomplete -c {0} -n 'not {1}_needs_code' -a '({1}_arguments)'\n",
+            command, prefix
+        ));
+
+        for (word, description) in options.iter().chain(codes.iter()) {
+            script.push_str(&format!(
+                "complete -c {0} -n {1}_needs_code -a {2} -d {3}\n",
+                command,
+                prefix,
+                quote(word),
+                quote(description)
+            ));
+        }
+    }
+
+    script
+}
+
+/// Return the PowerShell completion script for both the command and the command with the .exe extension.
+fn powershell(name: &str, codes: &[(String, String)], options: &[(String, String)]) -> String {
+    let quote = |text: &str| format!("'{0}'", text.replace('\'', "''"));
+    let pairs = |list: &[(String, String)]| {
+        list.iter()
+            .map(|(word, description)| format!("        ,@({0}, {1})", quote(word), quote(description)))
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+
+    format!(
+        r#"# PowerShell completion for {0}, generated by {0} completions powershell
+Register-ArgumentCompleter -Native -CommandName {0}, {0}.exe -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $codes = @(
+{1}
+    )
+    $options = @(
+{2}
+    )
+
+    # options come before the command code, and arguments for the command follow it
+    $previous = @($commandAst.CommandElements | Select-Object -Skip 1 |
+        Where-Object {{ $_.Extent.EndOffset -lt $cursorPosition }} | ForEach-Object {{ $_.ToString() }})
+
+    if ($previous | Where-Object {{ $_ -notlike '-*' }}) {{
+        & {0} __complete @previous "$wordToComplete" 2>$null | ForEach-Object {{
+            $value, $description = $_ -split "`t", 2
+            $text = if ($value -match '\s') {{ "'$value'" }} else {{ $value }}
+            [System.Management.Automation.CompletionResult]::new($text, $value, 'ParameterValue', $(if ($description) {{ $description }} else {{ $value }}))
+        }}
+        return
+    }}
+
+    $candidates = if ($wordToComplete -like '-*') {{ $options }} else {{ $codes }}
+    $candidates | Where-Object {{ $_[0] -like "$wordToComplete*" }} | ForEach-Object {{
+        [System.Management.Automation.CompletionResult]::new($_[0], $_[0], 'ParameterValue', $_[1])
+    }}
+}}
+"#,
+        name,
+        pairs(codes),
+        pairs(options)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::completions::shell::Shell;
+
+    fn pairs(list: &[(&str, &str)]) -> Vec<(String, String)> {
+        list.iter().map(|(w, d)| (w.to_string(), d.to_string())).collect()
+    }
+
+    #[test]
+    fn it_writes_completion_scripts() {
+        let codes = pairs(&[("word", "Microsoft Word"), ("it's", "Quote: 'this'")]);
+        let options = pairs(&[("-d", "dry (do not execute)")]);
+
+        let bash = Shell::Bash.script("wink", &codes, &options);
+        assert!(bash.contains(r#"compgen -W 'word it'\''s' -- "$cur""#));
+        assert!(bash.contains("complete -o default -F _wink wink wink.exe"));
+
+        let zsh = Shell::Zsh.script("wink", &codes, &options);
+        assert!(zsh.contains(r"'it'\''s:Quote: '\''this'\'''"));
+        assert!(zsh.contains("'-d:dry (do not execute)'"));
+
+        let fish = Shell::Fish.script("wink", &codes, &options);
+        assert!(fish.contains(r"complete -c wink -n __wink_needs_code -a 'it\'s' -d 'Quote: \'this\''"));
+        assert!(fish.contains(r"complete -c wink.exe -n __wink_needs_code -a 'it\'s' -d 'Quote: \'this\''"));
+        assert!(fish.contains("complete -c wink.exe -n 'not __wink_needs_code' -a '(__wink_arguments)'"));
+
+        let powershell = Shell::PowerShell.script("wink", &codes, &options);
+        assert!(powershell.contains(",@('it''s', 'Quote: ''this''')"));
+        assert_eq!(Shell::from_name("PWSH"), Some(Shell::PowerShell));
+        assert_eq!(Shell::from_name("csh"), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn it_completes_words_with_colons_in_bash() {
+        // a wink function stands in for wink __complete, completing the last word to itself with a slash
+        let bash = Shell::Bash.script("wink", &pairs(&[("word", "Microsoft Word")]), &[]);
+        let script = format!(
+            r#"{0}
+wink() {{ printf '%s/\n' "${{@: -1}}"; }}
+COMP_LINE='wink -d firefox https://exa'
+COMP_POINT=${{#COMP_LINE}}
+_wink 2>/dev/null
+printf '%s\n' "${{COMPREPLY[@]}}""#,
+            bash
+        );
+        let output = std::process::Command::new("bash").arg("-c").arg(script).output().unwrap();
+
+        // bash replaces only //exa, after the colon, so https: stays in the line
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "//exa/\n");
+    }
+}
@@ -0,0 +1,12 @@
+//! The wink.config module loads configuration files that add to or override
+//! the built-in list of categories and invocables.
+
+pub mod configcommand; // /src/config/configcommand.rs defines the wink config command
+pub mod configeditor; // /src/config/configeditor.rs defines config::configeditor::ConfigEditor
+pub mod configerror; // /src/config/configerror.rs defines config::configerror::ConfigError
+pub mod configformat; // /src/config/configformat.rs defines config::configformat::ConfigFormat
+pub mod configinclude; // /src/config/configinclude.rs defines config::configinclude::ConfigInclude
+pub mod configloader; // /src/config/configloader.rs defines config::configloader::ConfigLoader
+pub mod configsource; // /src/config/configsource.rs defines config::configsource::ConfigSource
+pub mod migration; // /src/config/migration.rs upgrades configuration files written for earlier versions
+pub mod schema; // /src/config/schema.rs generates a JSON Schema for configuration files
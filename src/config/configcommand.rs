@@ -0,0 +1,384 @@
+//! The wink config command maintains configuration files, such as wink config migrate,
+//! and adds, edits, removes, moves, and lists the invocables that they define.
+
+use crate::config::configeditor::ConfigEditor; // /src/config/configeditor.rs
+use crate::config::configerror::{ConfigError, ConfigErrorKind}; // /src/config/configerror.rs
+use crate::config::configformat::{ConfigFormat, CONFIG_FILE_NAMES}; // /src/config/configformat.rs
+use crate::config::configloader::{same_file, WINK_CONFIG_VARIABLE}; // /src/config/configloader.rs
+use crate::config::migration::{migrate, FORMAT_VERSION}; // /src/config/migration.rs
+use crate::winkconfig::WinkConfig; // /src/winkconfig.rs
+use crate::wsl::get_config_file_path; // /src/wsl.rs
+use crate::wsl::inv::invocablecategorylist::InvocableCategoryList; // /src/wsl/inv/invocablecategorylist.rs
+
+/// The command code that selects the wink config command.
+pub const CONFIG_COMMAND_CODE: &str = "config";
+
+/// Run the wink config action named by the first argument after config
+/// and return the exit code for the process.
+pub fn run(config: &WinkConfig) -> i32 {
+    match config.cmd_args.first().map(|a| a.as_str()) {
+        Some("migrate") => migrate_file(
+            &config
+                .cmd_args
+                .get(1)
+                .cloned()
+                .unwrap_or_else(default_config_file_path),
+        ),
+        Some(action) if EDIT_ACTIONS.contains(&action) => edit_file(action, &config.cmd_args[1..]),
+        Some(action) => {
+            eprintln!("Unrecognized config action: {0}", action);
+            usage(config);
+            1
+        }
+        None => {
+            usage(config);
+            1
+        }
+    }
+}
+
+/// Write usage information for the wink config command to stderr.
+fn usage(config: &WinkConfig) {
+    eprintln!(
+        "{0} config migrate [path] : upgrade configuration file to format_version {1}",
+        config.cmd_name, FORMAT_VERSION
+    );
+    eprintln!(
+        "{0} config add <code> --category <name> --command <command> [--alias <code>]... [--description <text>] [--mode <mode>] [--arg <argument>]... [--tag <tag>]... [--keywords <text>]",
+        config.cmd_name
+    );
+    eprintln!(
+        "{0} config edit <code> [--category <name>] [--command <command>] [--alias <code>]... [--description <text>] [--mode <mode>] [--arg <argument>]... [--tag <tag>]... [--keywords <text>]",
+        config.cmd_name
+    );
+    eprintln!("{0} config remove <code>", config.cmd_name);
+    eprintln!("{0} config move <code> <category>", config.cmd_name);
+    eprintln!("{0} config list [--user]", config.cmd_name);
+    eprintln!(
+        "{0:>12} : add, edit, remove, and move change the user configuration file, or the file named by --file <path>",
+        ""
+    );
+    eprintln!(
+        "{0:>12} : --force saves a YAML file with comments, which saving removes",
+        ""
+    );
+}
+
+/// The config actions that change or list invocables.
+pub const EDIT_ACTIONS: [&str; 5] = ["add", "edit", "remove", "move", "list"];
+
+/// The options of the config actions that change or list invocables,
+/// such as config edit word --arg /q --arg /n.
+#[derive(Default)]
+struct EditOptions {
+    /// The command code of the invocable to change.
+    code: String,
+
+    /// The category for add and move, or to move the invocable to for edit.
+    category: String,
+
+    /// The configuration file to change, or empty for the user configuration file.
+    file: String,
+
+    /// For list, true to list only the invocables in the user configuration file.
+    user: bool,
+
+    /// True to save a YAML file with comments, which saving removes.
+    force: bool,
+
+    /// The fields of the invocable to change, in the order of the Invocable struct.
+    changes: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Return the options in the given arguments, which accept both --name value and --name=value.
+fn parse_options(args: &[String]) -> Result<EditOptions, String> {
+    let mut options = EditOptions::default();
+    let mut positional: Vec<&str> = vec![];
+    let mut aliases: Option<Vec<String>> = None;
+    let mut description = None;
+    let mut command = None;
+    let mut mode = None;
+    let mut arguments: Option<Vec<String>> = None;
+    let mut tags: Option<Vec<String>> = None;
+    let mut keywords = None;
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        let (name, inline) = match arg.strip_prefix("--") {
+            Some(option) => match option.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (option, None),
+            },
+            None => {
+                positional.push(arg);
+                continue;
+            }
+        };
+
+        if name == "user" || name == "force" {
+            options.user |= name == "user";
+            options.force |= name == "force";
+            continue;
+        }
+
+        let value = match inline.or_else(|| iter.next().cloned()) {
+            Some(value) => value,
+            None => return Err(format!("--{0} requires a value", name)),
+        };
+
+        match name {
+            "category" => options.category = value,
+            "file" => options.file = value,
+            "alias" => aliases.get_or_insert_with(Vec::new).push(value),
+            "command" => command = Some(value),
+            "description" => description = Some(value),
+            "mode" => mode = Some(value),
+            "arg" => arguments.get_or_insert_with(Vec::new).push(value),
+            "tag" => tags.get_or_insert_with(Vec::new).push(value),
+            "keywords" => keywords = Some(value),
+            _ => return Err(format!("Unrecognized config option: --{0}", name)),
+        }
+    }
+
+    for (key, value) in [
+        ("aliases", aliases.map(serde_json::Value::from)),
+        ("description", description.map(serde_json::Value::from)),
+        ("command", command.map(serde_json::Value::from)),
+        ("mode", mode.map(serde_json::Value::from)),
+        ("arguments", arguments.map(serde_json::Value::from)),
+        ("tags", tags.map(serde_json::Value::from)),
+        ("keywords", keywords.map(serde_json::Value::from)),
+    ] {
+        if let Some(value) = value {
+            options.changes.insert(key.to_string(), value);
+        }
+    }
+
+    options.code = positional.first().map(|c| c.to_string()).unwrap_or_default();
+
+    if let Some(category) = positional.get(1) {
+        options.category = category.to_string();
+    }
+
+    if positional.len() > 2 {
+        return Err(format!("Unexpected argument: {0}", positional[2]));
+    }
+
+    Ok(options)
+}
+
+/// Add, edit, remove, move, or list invocables and return the exit code for the process.
+fn edit_file(action: &str, args: &[String]) -> i32 {
+    match try_edit_file(action, args) {
+        Ok(message) => {
+            println!("{}", message);
+            0
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            1
+        }
+    }
+}
+
+fn try_edit_file(action: &str, args: &[String]) -> Result<String, String> {
+    let options = parse_options(args)?;
+    let path = if options.file.is_empty() {
+        default_config_file_path()
+    } else {
+        options.file.clone()
+    };
+    let mut editor = ConfigEditor::open(&path).map_err(|e| e.to_string())?;
+    let category_list = editor.merged().map_err(|e| e.to_string())?;
+
+    if action == "list" {
+        return Ok(list(&editor, &category_list, options.user));
+    }
+
+    if options.code.is_empty() {
+        return Err(format!("config {0} requires a command code", action));
+    }
+
+    let code = options.code.as_str();
+    let message = match action {
+        "add" => {
+            if options.category.is_empty() || !options.changes.contains_key("command") {
+                return Err("config add requires --category and --command".to_string());
+            }
+
+            editor.add(code, &options.category, &options.changes)
+                .map(|_| format!("Added {0} to {1}", code, options.category))
+        }
+        "edit" => editor.edit(code, &options.changes, &category_list).and_then(|_| {
+            if options.category.is_empty() {
+                Ok(format!("Changed {0}", code))
+            } else {
+                editor
+                    .move_to(code, &options.category, &category_list)
+                    .map(|_| format!("Changed {0} and moved it to {1}", code, options.category))
+            }
+        }),
+        "remove" => editor.remove(code, &category_list).map(|removed| match removed {
+            true => format!("Removed {0}", code),
+            false => format!("Disabled {0}", code),
+        }),
+        _ => {
+            if options.category.is_empty() {
+                return Err("config move requires a category".to_string());
+            }
+
+            editor
+                .move_to(code, &options.category, &category_list)
+                .map(|_| format!("Moved {0} to {1}", code, options.category))
+        }
+    }
+    .map_err(|e| e.to_string())?;
+
+    // do not save anything that wink cannot load
+    let merged = editor.merged().map_err(|e| format!("{0}; {1} not changed", e, path))?;
+    let backup = editor.save(options.force).map_err(|e| e.to_string())?;
+    let mut result = format!("{0} in {1}", message, path);
+
+    if let Some(backup) = backup {
+        result += &format!("; previous version saved as {0}", backup);
+    }
+
+    if let Some(invocable) = merged.get_invocable(code) {
+        if !invocable.source.is_built_in() && !same_file(&invocable.source.path, &path) {
+            result += &format!("\nNote: {0} configuration {1} also defines {2}", invocable.source.layer, invocable.source.path, code);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Return a line for each invocable in the given list, or only those in the user configuration file,
+/// with its category, the description or command, and the configuration file that defined it.
+fn list(editor: &ConfigEditor, category_list: &InvocableCategoryList, user: bool) -> String {
+    let mut lines: Vec<String> = vec![];
+
+    for category in category_list.categories.iter() {
+        for invocable in category.invocables.iter() {
+            if user && !same_file(&invocable.source.path, &editor.path) {
+                continue;
+            }
+
+            let desc = if invocable.description.is_empty() {
+                &invocable.command
+            } else {
+                &invocable.description
+            };
+            let mut line = format!("{0:>12} : {1} : {2}", invocable.command_code, category.name, desc);
+
+            if !user && !invocable.source.is_built_in() {
+                line += &format!(" [{0}]", invocable.source);
+            }
+
+            lines.push(line);
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Return the file named by the WINK_CONFIG environment variable, wink.json in the directory that it names,
+/// the first user configuration file that exists, or the path to wink.json if none exists.
+fn default_config_file_path() -> String {
+    if let Ok(path) = std::env::var(WINK_CONFIG_VARIABLE) {
+        if std::path::Path::new(&path).is_dir() {
+            return std::path::Path::new(&path)
+                .join(CONFIG_FILE_NAMES[0])
+                .to_string_lossy()
+                .to_string();
+        } else if !path.is_empty() {
+            return path;
+        }
+    }
+
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| get_config_file_path(name))
+        .find(|path| std::path::Path::new(path).is_file())
+        .unwrap_or_else(|| get_config_file_path(CONFIG_FILE_NAMES[0]))
+}
+
+/// Upgrade the configuration file at the given path to the current format_version,
+/// saving the original file with a .bak suffix, after rotating earlier backups, and keeping its format.
+fn migrate_file(path: &str) -> i32 {
+    match try_migrate_file(path) {
+        Ok(message) => {
+            println!("{}", message);
+            0
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            1
+        }
+    }
+}
+
+fn try_migrate_file(path: &str) -> Result<String, ConfigError> {
+    let format = ConfigFormat::from_path(path);
+    let data = std::fs::read_to_string(path).map_err(|e| ConfigError::from_io(path, &e))?;
+    let mut document = format.parse(path, &data)?;
+    let version = migrate(&mut document)
+        .map_err(|e| ConfigError::new(ConfigErrorKind::InvalidValue, path, e))?;
+
+    if version == FORMAT_VERSION {
+        return Ok(format!("{0} is already format_version {1}", path, FORMAT_VERSION));
+    }
+
+    // do not write anything that wink cannot read
+    serde_json::from_value::<InvocableCategoryList>(document.clone())
+        .map_err(|e| ConfigError::from_json(path, &e).locate(&data))?;
+
+    let migrated = format
+        .to_string(&document, true)
+        .map_err(|e| ConfigError::new(ConfigErrorKind::InvalidValue, path, e))?;
+    let backup = ConfigEditor::write(path, &(migrated + "\n"))?.unwrap_or_default();
+
+    Ok(format!(
+        "Migrated {0} from format_version {1} to {2}; original saved as {3}",
+        path, version, FORMAT_VERSION, backup
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_migrates_files_with_a_backup() {
+        let path = std::env::temp_dir().join("wink-configcommand-migrate.json");
+        let path = path.to_string_lossy().to_string();
+        let original = r#"{ "categories": [ { "name": "Mine", "invocables": [ { "command_code": "pc", "command": "shell:MyComputerFolder", "use_explorer": true } ] } ] }"#;
+        std::fs::write(&path, original).unwrap();
+
+        assert_eq!(super::migrate_file(&path), 0);
+        let migrated = std::fs::read_to_string(&path).unwrap();
+        let backup = std::fs::read_to_string(format!("{0}.bak", path)).unwrap();
+        assert_eq!(super::migrate_file(&path), 0);
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(format!("{0}.bak", path)).unwrap();
+
+        assert_eq!(backup, original);
+        assert!(migrated.starts_with("{\n  \"format_version\": 2,"));
+        assert!(migrated.contains("\"mode\": \"explorer\""));
+        assert!(!migrated.contains("use_explorer"));
+    }
+
+    #[test]
+    fn it_migrates_toml_files_as_toml() {
+        let path = std::env::temp_dir().join("wink-configcommand-migrate.toml");
+        let path = path.to_string_lossy().to_string();
+        let original = "[[categories]]\nname = \"Mine\"\n\n[[categories.invocables]]\ncommand_code = \"pc\"\ncommand = \"shell:MyComputerFolder\"\nuse_explorer = true\n";
+        std::fs::write(&path, original).unwrap();
+
+        assert_eq!(super::migrate_file(&path), 0);
+        let migrated = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(format!("{0}.bak", path)).unwrap();
+
+        assert!(migrated.starts_with("format_version = 2\n"), "{}", migrated);
+        assert!(migrated.contains("mode = \"explorer\""));
+        assert!(!migrated.contains("use_explorer"));
+    }
+}
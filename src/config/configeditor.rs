@@ -0,0 +1,736 @@
+//! A ConfigEditor changes the invocables in a configuration file, such as for wink config add.
+//! It keeps the order of the existing categories, invocables, and fields in the file,
+//! validates each change against the invocables that wink would load with the changed file,
+//! and saves the file atomically after rotating backups of the previous versions.
+//! Saving a TOML file keeps its comments and formatting; saving a YAML file would remove its comments,
+//! so a YAML file with comments is only saved when forced.
+
+use crate::config::configerror::{ConfigError, ConfigErrorKind}; // /src/config/configerror.rs
+use crate::config::configformat::ConfigFormat; // /src/config/configformat.rs
+use crate::config::configloader::{same_file, ConfigLoader}; // /src/config/configloader.rs
+use crate::config::configsource::{ConfigLayer, ConfigSource}; // /src/config/configsource.rs
+use crate::config::migration::{migrate, FORMAT_VERSION}; // /src/config/migration.rs
+use crate::wsl::inv::invocablecategorylist::InvocableCategoryList; // /src/wsl/inv/invocablecategorylist.rs
+
+/// The number of previous versions of a file to keep: file.bak, then file.bak.1, and so on.
+pub const BACKUP_COUNT: usize = 3;
+
+pub struct ConfigEditor {
+    /// The path to the configuration file.
+    pub path: String,
+
+    /// The format of the configuration file.
+    format: ConfigFormat,
+
+    /// The configuration document, upgraded to the current format_version.
+    document: serde_json::Value,
+
+    /// The contents of the file when it was opened, or empty if it did not exist.
+    original: String,
+}
+
+impl ConfigEditor {
+    /// Return a ConfigEditor for the configuration file at the given path,
+    /// or for an empty configuration document if the file does not exist.
+    pub fn open(path: &str) -> Result<ConfigEditor, ConfigError> {
+        let format = ConfigFormat::from_path(path);
+        let mut original = String::new();
+        let document = if std::path::Path::new(path).exists() {
+            original = std::fs::read_to_string(path).map_err(|e| ConfigError::from_io(path, &e))?;
+            let mut document = format.parse(path, &original)?;
+            migrate(&mut document).map_err(|e| ConfigError::new(ConfigErrorKind::InvalidValue, path, e))?;
+            document
+        } else {
+            serde_json::json!({ "format_version": FORMAT_VERSION, "categories": [] })
+        };
+
+        if !document.is_object() {
+            return Err(ConfigError::new(
+                ConfigErrorKind::InvalidValue,
+                path,
+                "expected a configuration document with categories".to_string(),
+            ));
+        }
+
+        Ok(ConfigEditor {
+            path: path.to_string(),
+            format,
+            document,
+            original,
+        })
+    }
+
+    /// Return the configuration document in this file.
+    pub fn document(&self) -> Result<InvocableCategoryList, ConfigError> {
+        serde_json::from_value(self.document.clone()).map_err(|e| ConfigError::from_json(&self.path, &e))
+    }
+
+    /// Return the invocables that wink would load with the changed file in place of the file on disk,
+    /// or the first problem with the changed file.
+    pub fn merged(&self) -> Result<InvocableCategoryList, ConfigError> {
+        let document = self.document()?;
+        let mut codes: Vec<&str> = vec![];
+
+        for code in document.categories.iter().flat_map(|c| c.invocables.iter()).flat_map(|i| i.codes()) {
+            if codes.iter().any(|c| c.eq_ignore_ascii_case(code)) {
+                return Err(ConfigError::new(
+                    ConfigErrorKind::InvalidValue,
+                    &self.path,
+                    format!("command code {0} defined more than once", code),
+                ));
+            }
+
+            codes.push(code);
+        }
+
+        let mut loader = ConfigLoader::discover();
+
+        if !loader.sources.iter().any(|s| same_file(&s.path, &self.path)) {
+            loader.sources.push(ConfigSource::new(ConfigLayer::User, &self.path));
+        }
+
+        loader.documents.push((self.path.clone(), document.clone()));
+        let mut category_list = InvocableCategoryList::built_in();
+
+        // wink reports problems with other files whenever it runs, so only problems with this file prevent changes
+        match loader.load(&mut category_list).into_iter().find(|e| same_file(&e.path, &self.path)) {
+            Some(error) => Err(error),
+            None => Ok(category_list),
+        }
+    }
+
+    /// Add an invocable with the given command code and fields to the given category in this file,
+    /// creating the category if necessary.
+    pub fn add(&mut self, code: &str, category: &str, changes: &serde_json::Map<String, serde_json::Value>) -> Result<(), ConfigError> {
+        self.check_reserved(code, changes)?;
+
+        if self.find(code).is_some() {
+            return Err(self.error(format!("command code {0} already defined; use edit to change it", code)));
+        }
+
+        let mut invocable = serde_json::Map::new();
+        invocable.insert("command_code".to_string(), serde_json::json!(code));
+        apply(&mut invocable, changes);
+        self.insert(category, invocable);
+        Ok(())
+    }
+
+    /// Change the fields of the invocable with the given command code, ignoring case. If this file does not define it,
+    /// copy it from the given list, such as a built-in invocable, to replace it.
+    pub fn edit(
+        &mut self,
+        code: &str,
+        changes: &serde_json::Map<String, serde_json::Value>,
+        category_list: &InvocableCategoryList,
+    ) -> Result<(), ConfigError> {
+        self.check_reserved("", changes)?;
+
+        let (category, index) = match self.find(code) {
+            Some(found) => found,
+            None => {
+                let (category, invocable) = self.copy(code, category_list)?;
+                let copied = invocable["command_code"].as_str().unwrap_or(code).to_string();
+                self.insert(&category, invocable);
+                self.find(&copied).unwrap()
+            }
+        };
+
+        let invocable = self.invocable_mut(category, &index);
+
+        // expand the shorthand "code": "command" to an invocable that can hold other fields
+        if let serde_json::Value::String(command) = invocable {
+            *invocable = serde_json::json!({ "command": command });
+        }
+
+        apply(invocable.as_object_mut().unwrap(), changes);
+        Ok(())
+    }
+
+    /// Remove the invocable with the given command code from this file, or disable it if another layer defines it.
+    /// Return true if this file defined the invocable.
+    pub fn remove(&mut self, code: &str, category_list: &InvocableCategoryList) -> Result<bool, ConfigError> {
+        if self.take(code).is_some() {
+            return Ok(true);
+        }
+
+        let code = match category_list.get_invocable(code) {
+            Some(invocable) => invocable.command_code.clone(),
+            None => return Err(self.error(format!("unknown command code {0}", code))),
+        };
+
+        let disabled = self.object_mut().entry("disabled_codes").or_insert_with(|| serde_json::json!([]));
+
+        if let Some(codes) = disabled.as_array_mut() {
+            if !codes.iter().any(|c| c.as_str().is_some_and(|c| c.eq_ignore_ascii_case(&code))) {
+                codes.push(serde_json::json!(code));
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Move the invocable with the given command code to the given category, creating the category if necessary.
+    /// If this file does not define it, copy it from the given list to replace it in that category.
+    pub fn move_to(&mut self, code: &str, category: &str, category_list: &InvocableCategoryList) -> Result<(), ConfigError> {
+        let invocable = match self.take(code) {
+            Some((_category, invocable)) => invocable,
+            None => self.copy(code, category_list)?.1,
+        };
+
+        self.insert(category, invocable);
+        Ok(())
+    }
+
+    /// Save the changes to the file, keeping the comments and formatting of a TOML file.
+    /// Refuse to save a YAML file with comments, which saving would remove, unless forced.
+    pub fn save(&self, force: bool) -> Result<Option<String>, ConfigError> {
+        let contents = self
+            .format
+            .to_string(&self.document, true)
+            .map_err(|e| self.error(e))?;
+
+        let contents = match self.format {
+            ConfigFormat::Toml if !self.original.is_empty() => {
+                let parse = |data: &str| data.parse::<toml_edit::DocumentMut>().map_err(|e| self.error(e.to_string()));
+                let mut document = parse(&self.original)?;
+                let changed = unpositioned(parse(&contents)?.into_item()).into_table().unwrap();
+                merge_table(document.as_table_mut(), changed);
+                document.to_string()
+            }
+            ConfigFormat::Yaml if !force && has_yaml_comments(&self.original) => {
+                return Err(self.error(
+                    "saving would remove the comments in this file; use --force to save it anyway".to_string(),
+                ));
+            }
+            _ => contents + "\n",
+        };
+
+        ConfigEditor::write(&self.path, &contents)
+    }
+
+    /// Replace the contents of the file at the given path atomically, writing through symbolic links,
+    /// after rotating its backups and copying it to path.bak. Return the path to the backup, if any.
+    pub fn write(path: &str, contents: &str) -> Result<Option<String>, ConfigError> {
+        // $HOME/.wink.json can be a symbolic link to wink.json, which should remain a link
+        let target = std::fs::canonicalize(path)
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| path.to_string());
+        let mut backup = None;
+
+        if std::path::Path::new(&target).is_file() {
+            let name = |n: usize| match n {
+                0 => format!("{0}.bak", target),
+                _ => format!("{0}.bak.{1}", target, n),
+            };
+
+            for n in (1..BACKUP_COUNT).rev() {
+                if std::path::Path::new(&name(n - 1)).is_file() {
+                    std::fs::rename(name(n - 1), name(n)).map_err(|e| ConfigError::from_io(&name(n), &e))?;
+                }
+            }
+
+            std::fs::copy(&target, name(0)).map_err(|e| ConfigError::from_io(&name(0), &e))?;
+            backup = Some(name(0));
+        }
+
+        let temporary = format!("{0}.tmp", target);
+        std::fs::write(&temporary, contents).map_err(|e| ConfigError::from_io(&temporary, &e))?;
+
+        let replaced = match std::fs::metadata(&target) {
+            Ok(metadata) => std::fs::set_permissions(&temporary, metadata.permissions()),
+            Err(_) => Ok(()),
+        }
+        .and_then(|_| std::fs::rename(&temporary, &target));
+
+        if let Err(e) = replaced {
+            let _ = std::fs::remove_file(&temporary);
+            return Err(ConfigError::from_io(&target, &e));
+        }
+
+        Ok(backup)
+    }
+
+    /// Return an error if the given command code or any alias in the given changes is reserved
+    /// for a command that wink runs itself, such as config, which wink would run instead of the invocable.
+    fn check_reserved(&self, code: &str, changes: &serde_json::Map<String, serde_json::Value>) -> Result<(), ConfigError> {
+        let aliases = changes.get("aliases").and_then(|a| a.as_array()).into_iter().flatten();
+
+        match std::iter::once(code).chain(aliases.filter_map(|a| a.as_str())).find(|c| crate::is_reserved(c)) {
+            Some(reserved) => Err(self.error(format!("command code {0} is reserved for wink {0}", reserved))),
+            None => Ok(()),
+        }
+    }
+
+    /// Return a ConfigError about this file.
+    fn error(&self, message: String) -> ConfigError {
+        ConfigError::new(ConfigErrorKind::InvalidValue, &self.path, message)
+    }
+
+    fn object_mut(&mut self) -> &mut serde_json::Map<String, serde_json::Value> {
+        self.document.as_object_mut().unwrap()
+    }
+
+    /// Return the categories in this file, adding an empty list if there are none.
+    fn categories_mut(&mut self) -> &mut Vec<serde_json::Value> {
+        let categories = self.object_mut().entry("categories").or_insert_with(|| serde_json::json!([]));
+
+        if !categories.is_array() {
+            *categories = serde_json::json!([]);
+        }
+
+        categories.as_array_mut().unwrap()
+    }
+
+    /// Return the index of the category that defines the given command code in this file, ignoring case,
+    /// and the index or key of the invocable within the category.
+    fn find(&self, code: &str) -> Option<(usize, serde_json::Value)> {
+        let categories = self.document.get("categories")?.as_array()?;
+
+        for (index, category) in categories.iter().enumerate() {
+            match category.get("invocables") {
+                Some(serde_json::Value::Array(list)) => {
+                    let matches = |i: &serde_json::Value| i["command_code"].as_str().is_some_and(|c| c.eq_ignore_ascii_case(code));
+
+                    if let Some(position) = list.iter().position(matches) {
+                        return Some((index, serde_json::json!(position)));
+                    }
+                }
+                Some(serde_json::Value::Object(map)) => {
+                    if let Some(key) = map.keys().find(|k| k.eq_ignore_ascii_case(code)) {
+                        return Some((index, serde_json::json!(key)));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    /// Return the invocable in the given category at the given index or key from find().
+    fn invocable_mut(&mut self, category: usize, index: &serde_json::Value) -> &mut serde_json::Value {
+        let invocables = &mut self.categories_mut()[category]["invocables"];
+
+        match index {
+            serde_json::Value::Number(n) => &mut invocables[n.as_u64().unwrap() as usize],
+            _ => &mut invocables[index.as_str().unwrap()],
+        }
+    }
+
+    /// Remove the invocable with the given command code from this file, removing its category if that empties it,
+    /// and return the name of the category and the invocable, including its command code as this file spells it.
+    fn take(&mut self, code: &str) -> Option<(String, serde_json::Map<String, serde_json::Value>)> {
+        let (index, key) = self.find(code)?;
+        let category = &mut self.categories_mut()[index];
+        let name = category.get("name").and_then(|n| n.as_str()).unwrap_or_default().to_string();
+
+        let (code, removed) = match (&mut category["invocables"], key) {
+            (serde_json::Value::Array(list), serde_json::Value::Number(n)) => {
+                let removed = list.remove(n.as_u64().unwrap() as usize);
+                (removed["command_code"].as_str().unwrap_or(code).to_string(), removed)
+            }
+            (serde_json::Value::Object(map), serde_json::Value::String(k)) => {
+                let removed = map.shift_remove(&k).unwrap();
+                (k, removed)
+            }
+            _ => return None,
+        };
+
+        let empty = match &category["invocables"] {
+            serde_json::Value::Array(list) => list.is_empty(),
+            serde_json::Value::Object(map) => map.is_empty(),
+            _ => false,
+        };
+
+        if empty {
+            self.categories_mut().remove(index);
+        }
+
+        let mut invocable = serde_json::Map::new();
+        invocable.insert("command_code".to_string(), serde_json::json!(code));
+
+        match removed {
+            serde_json::Value::Object(fields) => invocable.extend(fields.into_iter().filter(|(k, _v)| k != "command_code")),
+            command => {
+                invocable.insert("command".to_string(), command);
+            }
+        }
+
+        Some((name, invocable))
+    }
+
+    /// Add the given invocable, including its command code, to the named category,
+    /// creating the category if necessary and following the list or map form of an existing category.
+    fn insert(&mut self, category: &str, mut invocable: serde_json::Map<String, serde_json::Value>) {
+        let categories = self.categories_mut();
+        let index = match categories.iter().position(|c| c.get("name").is_some_and(|n| n == category)) {
+            Some(index) => index,
+            None => {
+                categories.push(serde_json::json!({ "name": category, "invocables": [] }));
+                categories.len() - 1
+            }
+        };
+
+        match &mut categories[index]["invocables"] {
+            serde_json::Value::Object(map) => {
+                let code = invocable.shift_remove("command_code").unwrap();
+                map.insert(code.as_str().unwrap().to_string(), serde_json::Value::Object(invocable));
+            }
+            serde_json::Value::Array(list) => list.push(serde_json::Value::Object(invocable)),
+            other => *other = serde_json::json!([invocable]),
+        }
+    }
+
+    /// Return the name of the category and the fields of the invocable with the given command code or alias
+    /// in the given list, ignoring case, to copy into this file with the command code of that invocable.
+    fn copy(
+        &self,
+        code: &str,
+        category_list: &InvocableCategoryList,
+    ) -> Result<(String, serde_json::Map<String, serde_json::Value>), ConfigError> {
+        let invocable = match category_list.get_invocable(code) {
+            Some(invocable) => invocable,
+            None => return Err(self.error(format!("unknown command code {0}", code))),
+        };
+
+        for category in category_list.categories.iter() {
+            if category.invocables.iter().any(|i| std::ptr::eq(i, invocable)) {
+                let mut fields = serde_json::to_value(invocable).unwrap().as_object().unwrap().clone();
+                fields.shift_remove("source");
+                return Ok((category.name.clone(), fields));
+            }
+        }
+
+        Err(self.error(format!("unknown command code {0}", code)))
+    }
+}
+
+/// Apply the given changes to the fields of an invocable in place,
+/// removing fields changed to their defaults, such as an empty description or the direct mode.
+fn apply(invocable: &mut serde_json::Map<String, serde_json::Value>, changes: &serde_json::Map<String, serde_json::Value>) {
+    for (key, value) in changes.iter() {
+        let default = match value {
+            serde_json::Value::String(s) => s.is_empty() || (key == "mode" && s == "direct"),
+            serde_json::Value::Array(a) => a.is_empty(),
+            _ => false,
+        };
+
+        if default {
+            invocable.shift_remove(key);
+        } else {
+            invocable.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+/// Return true if the given YAML text appears to have comments. A # that starts a line or follows a space
+/// starts a comment, except within quotes, which this does not track, so it can mistake such a # for a comment.
+fn has_yaml_comments(data: &str) -> bool {
+    data.lines().any(|line| line.trim_start().starts_with('#') || line.contains(" #"))
+}
+
+/// Change the given TOML table in place to match the given table, keeping the comments, formatting,
+/// and order of the entries that remain.
+fn merge_table(table: &mut toml_edit::Table, changed: toml_edit::Table) {
+    table.retain(|key, _item| changed.contains_key(key));
+
+    for (key, item) in changed.into_iter() {
+        match table.get_mut(&key) {
+            Some(existing) if mergeable(existing, &item) => merge_item(existing, item),
+            _ => {
+                // remove the entry first so that a table does not keep the formatting of the key of a value
+                table.remove(&key);
+                table.insert(&key, item);
+            }
+        }
+    }
+}
+
+/// Return true if the given TOML item can be changed in place to match the given item,
+/// such as a table or an inline table to match a table.
+fn mergeable(item: &toml_edit::Item, changed: &toml_edit::Item) -> bool {
+    use toml_edit::Item;
+
+    match (item, changed) {
+        (Item::Table(_), Item::Table(_)) | (Item::ArrayOfTables(_), Item::ArrayOfTables(_)) => true,
+        (Item::Value(value), Item::Table(_)) => value.is_inline_table(),
+        (Item::Value(value), Item::ArrayOfTables(_)) => value.is_array(),
+        (Item::Value(_), Item::Value(_)) => true,
+        _ => false,
+    }
+}
+
+/// Change the given TOML item in place to match the given item, keeping the form of the existing item,
+/// such as an inline table rather than a table.
+fn merge_item(item: &mut toml_edit::Item, changed: toml_edit::Item) {
+    use toml_edit::Item;
+
+    match (item, changed) {
+        (Item::Table(table), Item::Table(changed)) => merge_table(table, changed),
+        (Item::ArrayOfTables(tables), Item::ArrayOfTables(changed)) => {
+            let existing = tables.iter().cloned().collect();
+            let merged = merge_list(existing, changed.into_iter().collect(), |t| identity(t.get("command_code").or_else(|| t.get("name")).and_then(|i| i.as_str())), merge_table);
+            tables.clear();
+            tables.extend(merged);
+        }
+        (Item::Value(value), Item::Table(changed)) => merge_value(value, toml_edit::Value::InlineTable(changed.into_inline_table())),
+        (Item::Value(value), Item::ArrayOfTables(changed)) => merge_value(value, toml_edit::Value::Array(changed.into_array())),
+        (Item::Value(value), Item::Value(changed)) => merge_value(value, changed),
+        (item, changed) => *item = changed,
+    }
+}
+
+/// Change the given TOML value in place to match the given value, keeping its formatting if it is equal.
+fn merge_value(value: &mut toml_edit::Value, changed: toml_edit::Value) {
+    use toml_edit::Value;
+
+    match (value, changed) {
+        (Value::InlineTable(table), Value::InlineTable(changed)) => {
+            let last = table.iter().last().and_then(|(_key, v)| v.decor().suffix().cloned());
+            table.retain(|key, _value| changed.contains_key(key));
+
+            for (key, value) in changed.into_iter() {
+                match table.get_mut(&key) {
+                    Some(existing) => merge_value(existing, value),
+                    None => {
+                        table.insert(key.as_str(), value);
+                    }
+                }
+            }
+
+            // keep the space before the closing brace after the last entry only
+            let count = table.len();
+
+            for (index, (_key, value)) in table.iter_mut().enumerate() {
+                if index + 1 == count {
+                    value.decor_mut().set_suffix(last.clone().unwrap_or_default());
+                } else if value.decor().suffix().and_then(|s| s.as_str()).is_some_and(|s| s.trim().is_empty()) {
+                    value.decor_mut().set_suffix("");
+                }
+            }
+        }
+        (Value::Array(array), Value::Array(changed)) => {
+            let existing: Vec<Value> = array.iter().cloned().collect();
+            let decors: Vec<toml_edit::Decor> = existing.iter().map(|v| v.decor().clone()).collect();
+            let merged = merge_list(existing, changed.into_iter().collect(), |v| identity(v.as_inline_table().and_then(|t| t.get("command_code").or_else(|| t.get("name"))).and_then(|i| i.as_str())), merge_value);
+            let count = merged.len();
+            array.clear();
+            array.extend(merged);
+
+            // space the elements as the existing elements were spaced, the first and last as they were
+            if let Some(last) = decors.last() {
+                for (index, value) in array.iter_mut().enumerate() {
+                    let prefix = decors[index.min(decors.len() - 1).min(1)].prefix().cloned().unwrap_or_default();
+                    let suffix = match index + 1 == count {
+                        true => last.suffix(),
+                        false => decors.first().filter(|_d| decors.len() > 1).and_then(|d| d.suffix()),
+                    };
+                    *value.decor_mut() = toml_edit::Decor::new(prefix, suffix.cloned().unwrap_or_default());
+                }
+            }
+        }
+        (value, changed) => {
+            let equal = match (&*value, &changed) {
+                (Value::String(a), Value::String(b)) => a.value() == b.value(),
+                (Value::Integer(a), Value::Integer(b)) => a.value() == b.value(),
+                (Value::Float(a), Value::Float(b)) => a.value() == b.value(),
+                (Value::Boolean(a), Value::Boolean(b)) => a.value() == b.value(),
+                (Value::Datetime(a), Value::Datetime(b)) => a.value() == b.value(),
+                _ => false,
+            };
+
+            if !equal {
+                let decor = value.decor().clone();
+                *value = changed;
+                *value.decor_mut() = decor;
+            }
+        }
+    }
+}
+
+/// Return the given command code or category name, ignoring case, to match entries in lists.
+fn identity(name: Option<&str>) -> Option<String> {
+    name.map(|n| n.to_lowercase())
+}
+
+/// Return the given changed list of TOML entries, with each entry merged into the existing entry
+/// with the same identity, such as the invocable with the same command code, or at the same position
+/// if neither has an identity, such as an argument.
+fn merge_list<T>(existing: Vec<T>, changed: Vec<T>, identity: fn(&T) -> Option<String>, merge: fn(&mut T, T)) -> Vec<T> {
+    let mut existing: Vec<Option<T>> = existing.into_iter().map(Some).collect();
+    let mut merged = vec![];
+
+    for (index, entry) in changed.into_iter().enumerate() {
+        let id = identity(&entry);
+        let position = match id {
+            Some(_) => existing.iter().position(|e| e.as_ref().is_some_and(|e| identity(e) == id)),
+            None => Some(index).filter(|i| existing.get(*i).is_some_and(|e| e.as_ref().is_some_and(|e| identity(e).is_none()))),
+        };
+
+        match position.and_then(|p| existing[p].take()) {
+            Some(mut found) => {
+                merge(&mut found, entry);
+                merged.push(found);
+            }
+            None => merged.push(entry),
+        }
+    }
+
+    merged
+}
+
+/// Return the given TOML item with the positions of its tables cleared,
+/// so that a table from another document follows the table before it in this document.
+fn unpositioned(mut item: toml_edit::Item) -> toml_edit::Item {
+    match &mut item {
+        toml_edit::Item::Table(table) => {
+            table.set_position(None);
+
+            for (_key, child) in table.iter_mut() {
+                *child = unpositioned(std::mem::take(child));
+            }
+        }
+        toml_edit::Item::ArrayOfTables(tables) => {
+            for table in tables.iter_mut() {
+                table.set_position(None);
+
+                for (_key, child) in table.iter_mut() {
+                    *child = unpositioned(std::mem::take(child));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    item
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::configeditor::ConfigEditor;
+
+    #[test]
+    #[cfg(feature = "cat-office")]
+    fn it_edits_invocables_in_place() {
+        use crate::wsl::inv::invocablecategorylist::InvocableCategoryList;
+        let changes = |json: serde_json::Value| json.as_object().unwrap().clone();
+        let path = std::env::temp_dir().join("wink-configeditor-edit.json");
+        let path = path.to_string_lossy().to_string();
+        let original = r#"{ "format_version": 2, "categories": [ { "name": "Mine", "invocables": { "np": "notepad.exe", "pc": { "command": "shell:MyComputerFolder", "mode": "explorer" } } } ] }"#;
+        std::fs::write(&path, original).unwrap();
+        let built_in = InvocableCategoryList::built_in();
+
+        let mut editor = ConfigEditor::open(&path).unwrap();
+        editor.edit("np", &changes(serde_json::json!({ "arguments": [ "/a" ] })), &built_in).unwrap();
+        editor.add("dir", "Shell", &changes(serde_json::json!({ "command": "dir", "mode": "cmd" }))).unwrap();
+        editor.move_to("PC", "Shell", &built_in).unwrap();
+
+        // command codes match ignoring case, and the file keeps the code as the matched invocable spells it
+        editor.edit("WORD", &changes(serde_json::json!({ "arguments": [ "/q" ] })), &built_in).unwrap();
+        assert!(!editor.remove("Excel", &built_in).unwrap());
+        assert!(!editor.remove("EXCEL", &built_in).unwrap());
+        assert!(editor.add("NP", "Mine", &changes(serde_json::json!({}))).is_err());
+        assert!(editor.add("Config", "Mine", &changes(serde_json::json!({ "command": "config.exe" }))).is_err());
+        assert!(editor.edit("np", &changes(serde_json::json!({ "aliases": [ "n", "docs" ] })), &built_in).is_err());
+        assert!(editor.remove("nonexistent", &built_in).is_err());
+        let document = editor.document().unwrap();
+        editor.save(false).unwrap();
+        let backup = std::fs::read_to_string(format!("{0}.bak", path)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(format!("{0}.bak", path)).unwrap();
+
+        assert_eq!(backup, original);
+        assert_eq!(
+            serde_json::to_value(&document).unwrap(),
+            serde_json::json!({ "format_version": 2, "categories": [
+                { "name": "Mine", "invocables": [ { "command_code": "np", "command": "notepad.exe", "arguments": [ "/a" ] } ] },
+                { "name": "Shell", "invocables": [
+                    { "command_code": "dir", "command": "dir", "mode": "cmd" },
+                    { "command_code": "pc", "command": "shell:MyComputerFolder", "mode": "explorer" } ] },
+                { "name": "Microsoft Office", "invocables": [
+                    { "command_code": "word", "description": "Microsoft Word", "command": "$pf64/Microsoft Office/root/Office16/WINWORD.EXE", "arguments": [ "/q" ], "keywords": "docx document" } ] } ],
+                "disabled_codes": [ "excel" ] })
+        );
+    }
+
+    #[test]
+    fn it_keeps_comments_when_saving() {
+        let changes = |json: serde_json::Value| json.as_object().unwrap().clone();
+        let path = std::env::temp_dir().join("wink-configeditor-comments.toml");
+        let path = path.to_string_lossy().to_string();
+        let original = "# my invocables\nformat_version = 2\n\n[[categories]]\nname = \"Mine\" # first\ninvocables = { np = 'notepad.exe' }\n";
+        std::fs::write(&path, original).unwrap();
+
+        let mut editor = ConfigEditor::open(&path).unwrap();
+        editor.add("pc", "Mine", &changes(serde_json::json!({ "command": "shell:MyComputerFolder", "mode": "explorer" }))).unwrap();
+        editor.save(false).unwrap();
+        let saved = std::fs::read_to_string(&path).unwrap();
+
+        let yaml = std::env::temp_dir().join("wink-configeditor-comments.yaml");
+        let yaml = yaml.to_string_lossy().to_string();
+        std::fs::write(&yaml, "# my invocables\nformat_version: 2\ncategories: []\n").unwrap();
+        let mut editor = ConfigEditor::open(&yaml).unwrap();
+        editor.add("np", "Mine", &changes(serde_json::json!({ "command": "notepad.exe" }))).unwrap();
+        let refused = editor.save(false).is_err();
+        let unchanged = std::fs::read_to_string(&yaml).unwrap();
+        editor.save(true).unwrap();
+
+        for file in [&path, &yaml] {
+            std::fs::remove_file(file).unwrap();
+            std::fs::remove_file(format!("{0}.bak", file)).unwrap();
+        }
+
+        assert_eq!(
+            saved,
+            "# my invocables\nformat_version = 2\n\n[[categories]]\nname = \"Mine\" # first\ninvocables = { np = 'notepad.exe', pc = { command = \"shell:MyComputerFolder\", mode = \"explorer\" } }\n"
+        );
+        assert!(refused);
+        assert_eq!(unchanged, "# my invocables\nformat_version: 2\ncategories: []\n");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn it_keeps_permissions_and_removes_temporary_files() {
+        use std::os::unix::fs::PermissionsExt;
+        let path = std::env::temp_dir().join("wink-configeditor-permissions.json");
+        let path = path.to_string_lossy().to_string();
+        std::fs::write(&path, "{}").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        ConfigEditor::write(&path, "{ }").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(format!("{0}.bak", path)).unwrap();
+
+        // a file cannot replace a directory
+        let directory = std::env::temp_dir().join("wink-configeditor-directory.json");
+        let directory = directory.to_string_lossy().to_string();
+        std::fs::create_dir_all(&directory).unwrap();
+        let failed = ConfigEditor::write(&directory, "{}").is_err();
+        let temporary = std::path::Path::new(&format!("{0}.tmp", directory)).exists();
+        std::fs::remove_dir(&directory).unwrap();
+
+        assert_eq!(mode, 0o600);
+        assert!(failed);
+        assert!(!temporary);
+    }
+
+    #[test]
+    fn it_rotates_backups() {
+        let path = std::env::temp_dir().join("wink-configeditor-rotate.json");
+        let path = path.to_string_lossy().to_string();
+
+        for n in 0..=super::BACKUP_COUNT + 1 {
+            ConfigEditor::write(&path, &n.to_string()).unwrap();
+        }
+
+        let read = |suffix: &str| std::fs::read_to_string(format!("{0}{1}", path, suffix)).unwrap();
+        assert_eq!(read(""), (super::BACKUP_COUNT + 1).to_string());
+        assert_eq!(read(".bak"), super::BACKUP_COUNT.to_string());
+        assert_eq!(read(".bak.2"), (super::BACKUP_COUNT - 2).to_string());
+        assert!(!std::path::Path::new(&format!("{0}.bak.{1}", path, super::BACKUP_COUNT)).exists());
+
+        for suffix in ["", ".bak", ".bak.1", ".bak.2"] {
+            std::fs::remove_file(format!("{0}{1}", path, suffix)).unwrap();
+        }
+    }
+}
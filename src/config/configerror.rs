@@ -0,0 +1,287 @@
+//! A ConfigError describes a problem with a configuration file,
+//! such as a syntax error or an unknown field, and where to find it in the file.
+
+use crate::suggest::closest; // /src/suggest.rs
+
+/// Words that people use for configuration fields that wink names differently.
+const FIELD_SYNONYMS: [(&str, &str); 7] = [
+    ("shell", "bash"),
+    ("sh", "bash"),
+    ("args", "arguments"),
+    ("params", "arguments"),
+    ("desc", "description"),
+    ("code", "command_code"),
+    ("exe", "command"),
+];
+
+/// The kinds of problems that a configuration file can have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigErrorKind {
+    /// The file could not be read.
+    Io,
+
+    /// The file is not well-formed.
+    Syntax,
+
+    /// The file contains a field that wink does not recognize.
+    UnknownField,
+
+    /// The file omits a field that wink requires.
+    MissingField,
+
+    /// A field has a value that wink does not accept.
+    InvalidValue,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConfigError {
+    /// The kind of problem.
+    pub kind: ConfigErrorKind,
+
+    /// The path to the configuration file.
+    pub path: String,
+
+    /// The line number of the problem in the file, starting at 1, or 0 if unknown.
+    pub line: usize,
+
+    /// The column number of the problem in the line, starting at 1, or 0 if unknown.
+    pub column: usize,
+
+    /// The offending field or value, or empty if the problem does not involve a single field.
+    pub key: String,
+
+    /// A description of the problem.
+    pub message: String,
+
+    /// The field that the file probably meant to use, or empty if there is no suggestion.
+    pub suggestion: String,
+}
+
+impl ConfigError {
+    /// Return a ConfigError of the given kind without position information.
+    pub fn new(kind: ConfigErrorKind, path: &str, message: String) -> ConfigError {
+        ConfigError {
+            kind,
+            path: path.to_string(),
+            line: 0,
+            column: 0,
+            key: String::new(),
+            message,
+            suggestion: String::new(),
+        }
+    }
+
+    /// Return a ConfigError for a file that could not be read.
+    pub fn from_io(path: &str, error: &std::io::Error) -> ConfigError {
+        ConfigError::new(ConfigErrorKind::Io, path, format!("unable to read file: {0}", error))
+    }
+
+    /// Return a ConfigError for a file that serde_json could not deserialize,
+    /// identifying the offending key and suggesting a replacement for unknown fields.
+    pub fn from_json(path: &str, error: &serde_json::Error) -> ConfigError {
+        static LOCATION: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        let text = compiled(&LOCATION, r" at line \d+ column \d+$")
+            .replace(&error.to_string(), "")
+            .to_string();
+
+        let mut result = match error.classify() {
+            serde_json::error::Category::Io => ConfigError::new(ConfigErrorKind::Io, path, text),
+            serde_json::error::Category::Syntax | serde_json::error::Category::Eof => {
+                ConfigError::new(ConfigErrorKind::Syntax, path, text)
+            }
+            serde_json::error::Category::Data => ConfigError::from_data_message(path, &text),
+        };
+
+        result.line = error.line();
+        result.column = error.column();
+        result
+    }
+
+    /// Return a ConfigError for a TOML file that the toml crate could not deserialize.
+    pub fn from_toml(path: &str, data: &str, error: &toml::de::Error) -> ConfigError {
+        let mut result = ConfigError::from_data_message(path, error.message().trim_end());
+
+        if let Some(span) = error.span() {
+            (result.line, result.column) = position(data, span.start);
+        }
+
+        result
+    }
+
+    /// Return a ConfigError for a YAML file that serde_yaml could not deserialize.
+    pub fn from_yaml(path: &str, error: &serde_yaml::Error) -> ConfigError {
+        // serde_yaml prefixes the path to the offending value, such as categories[0].invocables[1]:
+        static MESSAGE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        let text = compiled(&MESSAGE, r"^(?:[\w\[\]\.]+: )?(?P<message>.*?)(?: at line \d+ column \d+)?$")
+            .replace(&error.to_string(), "$message")
+            .to_string();
+        let mut result = ConfigError::from_data_message(path, &text);
+
+        if let Some(location) = error.location() {
+            result.line = location.line();
+            result.column = location.column();
+        }
+
+        result
+    }
+
+    /// Set the line and column of this error from the first occurrence of its key in the given file contents,
+    /// such as after deserializing an upgraded document that no longer corresponds to the file.
+    /// JSON quotes keys; TOML and YAML usually do not.
+    pub fn locate(mut self, data: &str) -> ConfigError {
+        if self.line == 0 && !self.key.is_empty() {
+            let offset = data.find(&format!("\"{0}\"", self.key)).or_else(|| {
+                regex::Regex::new(&format!(r"\b{0}\b", regex::escape(&self.key)))
+                    .unwrap()
+                    .find(data)
+                    .map(|m| m.start())
+            });
+
+            if let Some(offset) = offset {
+                (self.line, self.column) = position(data, offset);
+            }
+        }
+
+        self
+    }
+
+    /// Return a ConfigError for a message from serde about the structure of the document.
+    fn from_data_message(path: &str, text: &str) -> ConfigError {
+        static UNKNOWN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        static MISSING: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        static VARIANT: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        let unknown = compiled(&UNKNOWN, r"^unknown field `(?P<key>[^`]*)`, (?:expected|there are no fields)(?P<expected>.*)$");
+        let missing = compiled(&MISSING, r"^missing field `(?P<key>[^`]*)`");
+        let variant = compiled(&VARIANT, r"^unknown variant `(?P<value>[^`]*)`, expected (?P<expected>.*)$");
+
+        if let Some(captures) = unknown.captures(text) {
+            let key = captures["key"].to_string();
+            let expected = quoted(captures.name("expected").map_or("", |m| m.as_str()));
+            let mut result = ConfigError::new(
+                ConfigErrorKind::UnknownField,
+                path,
+                format!("unknown field `{0}`", key),
+            );
+            result.suggestion = suggest_field(&key, &expected);
+            result.key = key;
+            result
+        } else if let Some(captures) = variant.captures(text) {
+            let value = captures["value"].to_string();
+            let expected = quoted(captures.name("expected").unwrap().as_str());
+            let mut result = ConfigError::new(
+                ConfigErrorKind::InvalidValue,
+                path,
+                format!("unknown value `{0}`", value),
+            );
+            result.suggestion = suggest_field(&value, &expected);
+            result.key = value;
+            result
+        } else if let Some(captures) = missing.captures(text) {
+            let mut result = ConfigError::new(ConfigErrorKind::MissingField, path, text.to_string());
+            result.key = captures["key"].to_string();
+            result
+        } else {
+            ConfigError::new(ConfigErrorKind::InvalidValue, path, text.to_string())
+        }
+    }
+}
+
+/// Return the line and column, starting at 1, of the given byte offset in the given file contents.
+fn position(data: &str, offset: usize) -> (usize, usize) {
+    let before = &data[..offset];
+    (
+        before.matches('\n').count() + 1,
+        before.len() - before.rfind('\n').map_or(0, |n| n + 1) + 1,
+    )
+}
+
+/// Return the regular expression in the given cell, compiling the given pattern the first time.
+fn compiled(cell: &'static std::sync::OnceLock<regex::Regex>, pattern: &str) -> &'static regex::Regex {
+    cell.get_or_init(|| regex::Regex::new(pattern).unwrap())
+}
+
+/// Return the names quoted in backticks in a message from serde, such as `use_cmd`, `use_bash`.
+fn quoted(text: &str) -> Vec<&str> {
+    static QUOTED: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+    compiled(&QUOTED, r"`([^`]*)`")
+        .captures_iter(text)
+        .map(|c| c.get(1).unwrap().as_str())
+        .collect()
+}
+
+/// Return the expected field closest to the given unknown field, or empty if none is close,
+/// treating synonyms such as shell for bash as equivalent.
+fn suggest_field(key: &str, expected: &[&str]) -> String {
+    let mut normalized = key.to_lowercase();
+
+    for (synonym, name) in FIELD_SYNONYMS.iter() {
+        normalized = normalized
+            .split('_')
+            .map(|part| if part == *synonym { *name } else { part })
+            .collect::<Vec<&str>>()
+            .join("_");
+    }
+
+    closest(&normalized, expected).unwrap_or_default().to_string()
+}
+
+/// Render as path:line:column: message, did you mean `suggestion`?
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.path)?;
+
+        if self.line > 0 {
+            write!(f, ":{0}:{1}", self.line, self.column)?;
+        }
+
+        write!(f, ": {}", self.message)?;
+
+        if !self.suggestion.is_empty() {
+            write!(f, ", did you mean `{}`?", self.suggestion)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::configerror::{ConfigError, ConfigErrorKind};
+    use crate::wsl::inv::invocablecategorylist::InvocableCategoryList;
+
+    fn parse(data: &str) -> ConfigError {
+        let error = serde_json::from_str::<InvocableCategoryList>(data).unwrap_err();
+        ConfigError::from_json("wink.json", &error)
+    }
+
+    #[test]
+    fn it_suggests_a_field_for_an_unknown_field() {
+        let error = parse("{\"categories\": [{\"name\": \"Mine\", \"invocables\": [\n{\"command_code\": \"x\", \"use_shell\": true}]}]}");
+        assert_eq!(error.kind, ConfigErrorKind::UnknownField);
+        assert_eq!(error.key, "use_shell");
+        assert_eq!(error.suggestion, "use_bash");
+        assert_eq!(error.line, 2);
+        assert_eq!(
+            error.to_string(),
+            format!("wink.json:2:{0}: unknown field `use_shell`, did you mean `use_bash`?", error.column)
+        );
+    }
+
+    #[test]
+    fn it_suggests_a_value_for_an_unknown_launch_mode() {
+        let error = parse("{\"categories\": [{\"name\": \"Mine\", \"invocables\": [{\"command_code\": \"x\", \"mode\": \"shell\"}]}]}");
+        assert_eq!(error.kind, ConfigErrorKind::InvalidValue);
+        assert_eq!(error.key, "shell");
+        assert_eq!(error.suggestion, "bash");
+    }
+
+    #[test]
+    fn it_reports_syntax_errors_with_position() {
+        let error = parse("{\"categories\": [\n,]}");
+        assert_eq!(error.kind, ConfigErrorKind::Syntax);
+        assert_eq!(error.line, 2);
+        assert!(error.suggestion.is_empty());
+    }
+}
@@ -0,0 +1,165 @@
+//! A ConfigFormat identifies the file format of a configuration file, JSON, TOML, or YAML,
+//! from the extension of its name, and reads and writes documents in that format.
+
+use crate::config::configerror::{ConfigError, ConfigErrorKind}; // /src/config/configerror.rs
+
+/// The names of configuration files for each layer, in the order that wink applies them
+/// when a directory contains more than one.
+pub const CONFIG_FILE_NAMES: [&str; 4] = ["wink.json", "wink.toml", "wink.yaml", "wink.yml"];
+
+/// The names of the formats for the --format command line option.
+pub const CONFIG_FORMAT_NAMES: [&str; 3] = ["json", "toml", "yaml"];
+
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigFormat {
+    #[default]
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Return the format with the given name, such as toml, if any.
+    pub fn from_name(name: &str) -> Option<ConfigFormat> {
+        match name.to_lowercase().as_str() {
+            "json" => Some(ConfigFormat::Json),
+            "toml" => Some(ConfigFormat::Toml),
+            "yaml" | "yml" => Some(ConfigFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Return the format for the extension of the given path, treating unknown extensions as JSON.
+    pub fn from_path(path: &str) -> ConfigFormat {
+        std::path::Path::new(path)
+            .extension()
+            .and_then(|e| ConfigFormat::from_name(&e.to_string_lossy()))
+            .unwrap_or_default()
+    }
+
+    /// Return true if the extension of the given path names a configuration file format,
+    /// such as for files in a directory that can also contain other files.
+    pub fn is_config_file(path: &str) -> bool {
+        std::path::Path::new(path)
+            .extension()
+            .and_then(|e| ConfigFormat::from_name(&e.to_string_lossy()))
+            .is_some()
+    }
+
+    /// Return the name of this format for the --format command line option.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConfigFormat::Json => CONFIG_FORMAT_NAMES[0],
+            ConfigFormat::Toml => CONFIG_FORMAT_NAMES[1],
+            ConfigFormat::Yaml => CONFIG_FORMAT_NAMES[2],
+        }
+    }
+
+    /// Return the configuration document in the given file contents without interpreting its fields.
+    pub fn parse(&self, path: &str, data: &str) -> Result<serde_json::Value, ConfigError> {
+        // any well-formed document converts to a Value, so every error is a syntax error
+        self.deserialize(path, data).map_err(|mut e| {
+            e.kind = ConfigErrorKind::Syntax;
+            e
+        })
+    }
+
+    /// Deserialize the given file contents in this format, reporting errors with their positions in the file.
+    pub fn deserialize<T>(&self, path: &str, data: &str) -> Result<T, ConfigError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        match self {
+            ConfigFormat::Json => serde_json::from_str(data).map_err(|e| ConfigError::from_json(path, &e)),
+            ConfigFormat::Toml => toml::from_str(data).map_err(|e| ConfigError::from_toml(path, data, &e)),
+            ConfigFormat::Yaml => serde_yaml::from_str(data).map_err(|e| ConfigError::from_yaml(path, &e)),
+        }
+    }
+
+    /// Serialize the given value in this format. Pretty applies to JSON and to arrays in TOML;
+    /// YAML is always indented.
+    pub fn to_string<T>(&self, value: &T, pretty: bool) -> Result<String, String>
+    where
+        T: serde::Serialize,
+    {
+        let result = match (self, pretty) {
+            (ConfigFormat::Json, false) => serde_json::to_string(value).map_err(|e| e.to_string()),
+            (ConfigFormat::Json, true) => serde_json::to_string_pretty(value).map_err(|e| e.to_string()),
+            (ConfigFormat::Toml, false) => toml::to_string(value).map_err(|e| e.to_string()),
+            (ConfigFormat::Toml, true) => toml::to_string_pretty(value).map_err(|e| e.to_string()),
+            (ConfigFormat::Yaml, _) => serde_yaml::to_string(value).map_err(|e| e.to_string()),
+        }?;
+
+        // TOML and YAML end with a newline; JSON does not
+        Ok(result.trim_end().to_string())
+    }
+}
+
+impl std::fmt::Display for ConfigFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::configformat::ConfigFormat;
+    use crate::config::migration::migrate;
+    use crate::wsl::inv::invocablecategorylist::InvocableCategoryList;
+
+    #[test]
+    fn it_picks_the_format_from_the_extension() {
+        assert_eq!(ConfigFormat::from_path("/etc/wink/wink.toml"), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::from_path("/home/jw/.wink.yml"), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path("C:\\Users\\jw\\wink.YAML"), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path("/home/jw/.wink.json"), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::from_path("/home/jw/.wink"), ConfigFormat::Json);
+    }
+
+    #[test]
+    fn it_round_trips_every_format() {
+        let built_in = InvocableCategoryList::built_in();
+        let expected = serde_json::to_value(&built_in).unwrap();
+
+        for format in [ConfigFormat::Json, ConfigFormat::Toml, ConfigFormat::Yaml] {
+            for pretty in [false, true] {
+                let data = format.to_string(&built_in, pretty).unwrap();
+                let direct: InvocableCategoryList = format.deserialize("wink", &data).unwrap();
+                let mut document = format.parse("wink", &data).unwrap();
+                migrate(&mut document).unwrap();
+                let migrated: InvocableCategoryList = serde_json::from_value(document).unwrap();
+
+                assert_eq!(serde_json::to_value(&direct).unwrap(), expected, "{0}", format);
+                assert_eq!(serde_json::to_value(&migrated).unwrap(), expected, "{0}", format);
+            }
+        }
+    }
+
+    #[test]
+    fn it_reads_shorthand_toml_and_yaml() {
+        let toml = "format_version = 2\n\n[[categories]]\nname = \"Mine\"\n\n[categories.invocables]\npc = { command = \"shell:MyComputerFolder\", mode = \"explorer\" }\nnp = \"notepad.exe\"\n";
+        let yaml = "format_version: 2\ncategories:\n  - name: Mine\n    invocables:\n      pc: { command: \"shell:MyComputerFolder\", mode: explorer }\n      np: notepad.exe\n";
+        let from_toml: InvocableCategoryList = ConfigFormat::Toml.deserialize("wink.toml", toml).unwrap();
+        let from_yaml: InvocableCategoryList = ConfigFormat::Yaml.deserialize("wink.yaml", yaml).unwrap();
+
+        assert_eq!(serde_json::to_value(&from_toml).unwrap(), serde_json::to_value(&from_yaml).unwrap());
+        assert_eq!(from_toml.get_invocable("np").unwrap().command, "notepad.exe");
+    }
+
+    #[test]
+    fn it_reports_errors_with_positions() {
+        let toml = "[[categories]]\nname = \"Mine\"\n\n[[categories.invocables]]\ncommand_code = \"x\"\nuse_shell = true\n";
+        let error = ConfigFormat::Toml.deserialize::<InvocableCategoryList>("wink.toml", toml).unwrap_err();
+        assert_eq!(error.key, "use_shell");
+        assert_eq!(error.suggestion, "use_bash");
+        assert_eq!(error.line, 6);
+
+        let yaml = "categories:\n  - name: Mine\n    invocables:\n      - command_code: x\n        use_shell: true\n";
+        let error = ConfigFormat::Yaml.deserialize::<InvocableCategoryList>("wink.yaml", yaml).unwrap_err();
+        assert_eq!(error.key, "use_shell");
+        assert_eq!(error.suggestion, "use_bash");
+        assert_eq!(error.line, 5);
+        assert!(error.to_string().starts_with("wink.yaml:5:"), "{}", error);
+    }
+}
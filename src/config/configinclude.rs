@@ -0,0 +1,146 @@
+//! A ConfigInclude names other configuration files for a configuration file to apply before its own categories,
+//! such as command packs shared between teams. Configuration files specify each include as a path,
+//! such as "packs/office.json", or as a path with options, such as { "path": "packs/*.toml", "optional": true }.
+
+/// Another configuration file, or a glob pattern for other configuration files, to apply.
+#[derive(serde::Deserialize, schemars::JsonSchema, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(from = "IncludeDocument")]
+pub struct ConfigInclude {
+    /// The path or glob pattern, relative to the directory of the including file unless absolute.
+    pub path: String,
+
+    /// True to ignore the include if it matches no files.
+    pub optional: bool,
+}
+
+/// The path and options forms of includes in configuration files.
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+#[schemars(rename = "ConfigInclude")]
+enum IncludeDocument {
+    /// The path or glob pattern of files that must exist, relative to the directory of the including file unless absolute.
+    Path(String),
+
+    /// A path or glob pattern with options.
+    Entry(IncludeEntry),
+}
+
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct IncludeEntry {
+    /// The path or glob pattern, relative to the directory of the including file unless absolute.
+    path: String,
+
+    /// True to ignore the include if it matches no files. Defaults to false.
+    #[serde(default)]
+    optional: bool,
+}
+
+impl From<IncludeDocument> for ConfigInclude {
+    fn from(document: IncludeDocument) -> Self {
+        match document {
+            IncludeDocument::Path(path) => ConfigInclude { path, optional: false },
+            IncludeDocument::Entry(entry) => ConfigInclude {
+                path: entry.path,
+                optional: entry.optional,
+            },
+        }
+    }
+}
+
+/// Serialize as the path alone unless the include is optional.
+impl serde::Serialize for ConfigInclude {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        if self.optional {
+            let mut entry = serializer.serialize_struct("ConfigInclude", 2)?;
+            entry.serialize_field("path", &self.path)?;
+            entry.serialize_field("optional", &self.optional)?;
+            entry.end()
+        } else {
+            serializer.serialize_str(&self.path)
+        }
+    }
+}
+
+impl ConfigInclude {
+    /// Return the path or glob pattern of this include,
+    /// resolving a relative path against the directory of the including file at the given path.
+    pub fn pattern(&self, including: &str) -> String {
+        if std::path::Path::new(&self.path).is_absolute() {
+            return self.path.clone();
+        }
+
+        match std::path::Path::new(including).parent().map(|d| d.to_string_lossy().to_string()) {
+            // escape the directory so that characters such as [ in its name do not act as patterns
+            Some(dir) if !dir.is_empty() => format!(
+                "{0}{1}{2}",
+                glob::Pattern::escape(&dir),
+                std::path::MAIN_SEPARATOR,
+                self.path
+            ),
+            _ => self.path.clone(),
+        }
+    }
+
+    /// Return the paths of the files that this include names, in lexical order,
+    /// resolving relative paths against the directory of the including file at the given path,
+    /// or a message if the include matches no files and is not optional.
+    pub fn resolve(&self, including: &str) -> Result<Vec<String>, String> {
+        let mut paths: Vec<String> = glob::glob(&self.pattern(including))
+            .map_err(|e| format!("invalid include pattern: {0}", e))?
+            .filter_map(|p| p.ok())
+            .filter(|p| p.is_file())
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        paths.sort();
+
+        if paths.is_empty() && !self.optional {
+            return Err("include matches no files".to_string());
+        }
+
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::configinclude::ConfigInclude;
+
+    #[test]
+    fn it_reads_paths_and_entries() {
+        let includes: Vec<ConfigInclude> =
+            serde_json::from_str(r#"[ "packs/office.json", { "path": "packs/*.toml", "optional": true } ]"#).unwrap();
+        assert_eq!(includes[0], ConfigInclude { path: "packs/office.json".to_string(), optional: false });
+        assert_eq!(includes[1], ConfigInclude { path: "packs/*.toml".to_string(), optional: true });
+        assert_eq!(
+            serde_json::to_string(&includes).unwrap(),
+            r#"["packs/office.json",{"path":"packs/*.toml","optional":true}]"#
+        );
+        assert!(serde_json::from_str::<ConfigInclude>(r#"{ "path": "x.json", "required": true }"#).is_err());
+    }
+
+    #[test]
+    fn it_resolves_globs_relative_to_the_including_file() {
+        let dir = std::env::temp_dir().join("wink-configinclude-resolve");
+        std::fs::create_dir_all(dir.join("packs")).unwrap();
+        std::fs::write(dir.join("packs").join("b.json"), "{}").unwrap();
+        std::fs::write(dir.join("packs").join("a.json"), "{}").unwrap();
+        let including = dir.join("wink.json").to_string_lossy().to_string();
+
+        let glob = ConfigInclude { path: "packs/*.json".to_string(), optional: false }.resolve(&including);
+        let missing = ConfigInclude { path: "missing.json".to_string(), optional: false }.resolve(&including);
+        let optional = ConfigInclude { path: "missing.json".to_string(), optional: true }.resolve(&including);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let glob = glob.unwrap();
+        assert_eq!(glob.len(), 2);
+        assert!(glob[0].ends_with("a.json") && glob[1].ends_with("b.json"));
+        assert!(missing.is_err());
+        assert_eq!(optional, Ok(vec![]));
+    }
+}
@@ -0,0 +1,298 @@
+//! A ConfigLoader finds the configuration files for each layer
+//! and applies them to an InvocableCategoryList in order:
+//! system, then drop-in, then user, then project, where each layer can replace invocables from the layers before it.
+
+use crate::config::configerror::{ConfigError, ConfigErrorKind}; // /src/config/configerror.rs
+use crate::config::configformat::{ConfigFormat, CONFIG_FILE_NAMES}; // /src/config/configformat.rs
+use crate::config::configsource::{ConfigLayer, ConfigSource}; // /src/config/configsource.rs
+use crate::config::migration::{migrate, FORMAT_VERSION}; // /src/config/migration.rs
+use crate::wsl::inv::invocablecategorylist::InvocableCategoryList; // /src/wsl/inv/invocablecategorylist.rs
+use crate::wsl::{
+    find_project_config_file_paths, get_config_file_path, get_drop_in_config_dir_path, get_system_config_file_path,
+}; // /src/wsl.rs
+
+/// The environment variable that names a configuration file or directory
+/// to use instead of the drop-in directory and the user configuration file.
+pub const WINK_CONFIG_VARIABLE: &str = "WINK_CONFIG";
+
+pub struct ConfigLoader {
+    /// The configuration files to apply, in the order to apply them.
+    pub sources: Vec<ConfigSource>,
+
+    /// Documents to apply instead of reading the files at their paths, such as unsaved changes to a file.
+    pub documents: Vec<(String, InvocableCategoryList)>,
+}
+
+impl ConfigLoader {
+    /// Return a ConfigLoader for the system, drop-in, user, and project configuration files that exist,
+    /// in JSON, TOML, or YAML. If the WINK_CONFIG environment variable names a file or directory,
+    /// use it instead of the drop-in directory and the user configuration file.
+    pub fn discover() -> ConfigLoader {
+        let mut sources: Vec<ConfigSource> = vec![];
+
+        for name in CONFIG_FILE_NAMES.iter() {
+            let system = get_system_config_file_path(name);
+
+            if std::path::Path::new(&system).is_file() {
+                sources.push(ConfigSource::new(ConfigLayer::System, &system));
+            }
+        }
+
+        match std::env::var(WINK_CONFIG_VARIABLE) {
+            Ok(path) if !path.is_empty() => sources.extend(ConfigLoader::sources_at(&path)),
+            _ => {
+                sources.extend(ConfigLoader::sources_at(&get_drop_in_config_dir_path()));
+
+                for name in CONFIG_FILE_NAMES.iter() {
+                    let user = get_config_file_path(name);
+
+                    if std::path::Path::new(&user).is_file() {
+                        sources.push(ConfigSource::new(ConfigLayer::User, &user));
+                    }
+                }
+            }
+        }
+
+        // under $HOME, walking up from the current directory can find the user file again.
+        for project in find_project_config_file_paths(&CONFIG_FILE_NAMES) {
+            if !sources.iter().any(|s| same_file(&s.path, &project)) {
+                sources.push(ConfigSource::new(ConfigLayer::Project, &project));
+            }
+        }
+
+        ConfigLoader {
+            sources,
+            documents: vec![],
+        }
+    }
+
+    /// Return a drop-in source for each configuration file in the directory at the given path, in lexical order,
+    /// a user source for the file at the given path, or nothing if the path does not exist.
+    fn sources_at(path: &str) -> Vec<ConfigSource> {
+        let metadata = match std::fs::metadata(path) {
+            Ok(metadata) => metadata,
+            Err(_) => return vec![],
+        };
+
+        if !metadata.is_dir() {
+            return vec![ConfigSource::new(ConfigLayer::User, path)];
+        }
+
+        let mut files: Vec<String> = match std::fs::read_dir(path) {
+            Ok(entries) => entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_file())
+                .map(|e| e.path().to_string_lossy().to_string())
+                .filter(|p| ConfigFormat::is_config_file(p))
+                .collect(),
+            Err(e) => {
+                eprintln!("Ignoring configuration directory {0}: {1}", path, e);
+                vec![]
+            }
+        };
+
+        files.sort();
+        files.iter().map(|f| ConfigSource::new(ConfigLayer::DropIn, f)).collect()
+    }
+
+    /// Apply each configuration file to the given list, replacing invocables by command code
+    /// and merging categories by name. Skip any file that wink cannot read or parse
+    /// and return a ConfigError describing each.
+    pub fn load(&self, category_list: &mut InvocableCategoryList) -> Vec<ConfigError> {
+        let mut errors: Vec<ConfigError> = vec![];
+
+        for source in self.sources.iter() {
+            self.apply(source, category_list, &mut vec![], &mut vec![], &mut errors);
+        }
+
+        errors
+    }
+
+    /// Apply the configuration file for the given source to the given list after the files that it includes.
+    /// The chain lists the files that include this one, to detect cycles, and defined lists the command codes
+    /// that the files included by the same top-level file define, to report codes that more than one of them define.
+    fn apply(
+        &self,
+        source: &ConfigSource,
+        category_list: &mut InvocableCategoryList,
+        chain: &mut Vec<String>,
+        defined: &mut Vec<(String, String)>,
+        errors: &mut Vec<ConfigError>,
+    ) {
+        let replacement = self.documents.iter().find(|(path, _document)| same_file(path, &source.path));
+        let document = match replacement.map_or_else(|| ConfigLoader::read(&source.path), |(_path, d)| Ok(d.clone())) {
+            Ok(document) => document,
+            Err(e) => {
+                errors.push(e);
+                return;
+            }
+        };
+
+        chain.push(canonical(&source.path));
+
+        for include in document.includes.iter() {
+            let paths = include.resolve(&source.path).unwrap_or_else(|message| {
+                errors.push(ConfigError::new(
+                    ConfigErrorKind::Io,
+                    &include.pattern(&source.path),
+                    format!("{0} (included by {1})", message, source.path),
+                ));
+                vec![]
+            });
+
+            for path in paths {
+                if chain.contains(&canonical(&path)) {
+                    errors.push(ConfigError::new(
+                        ConfigErrorKind::InvalidValue,
+                        &path,
+                        format!("include cycle {0} -> {1}", chain.join(" -> "), canonical(&path)),
+                    ));
+                } else {
+                    self.apply(&source.include(&path), category_list, chain, defined, errors);
+                }
+            }
+        }
+
+        chain.pop();
+        document.report_duplicates(&source.path);
+
+        for category in document.categories.iter() {
+            for code in category.invocables.iter().flat_map(|i| i.codes()) {
+                match defined.iter().find(|(c, _path)| c.eq_ignore_ascii_case(code)) {
+                    Some((_code, path)) if *path != source.path => eprintln!(
+                        "Command code {0} defined in both {1} and {2}",
+                        code, path, source.path
+                    ),
+                    Some(_) => {}
+                    None => defined.push((code.clone(), source.path.clone())),
+                }
+            }
+        }
+
+        category_list.merge(document, source);
+    }
+
+    /// Return the InvocableCategoryList in the configuration file at the given path,
+    /// choosing JSON, TOML, or YAML from its extension and upgrading files written for earlier versions of wink.
+    pub fn read(path: &str) -> Result<InvocableCategoryList, ConfigError> {
+        let format = ConfigFormat::from_path(path);
+        let data = std::fs::read_to_string(path).map_err(|e| ConfigError::from_io(path, &e))?;
+        let mut document = format.parse(path, &data)?;
+        let version = migrate(&mut document).map_err(|e| {
+            let mut error = ConfigError::new(ConfigErrorKind::InvalidValue, path, e);
+            error.key = "format_version".to_string();
+            error.locate(&data)
+        })?;
+
+        // deserialize current files from their text to report errors with positions
+        if version == FORMAT_VERSION {
+            format.deserialize(path, &data).map_err(|e| e.locate(&data))
+        } else {
+            serde_json::from_value(document).map_err(|e| ConfigError::from_json(path, &e).locate(&data))
+        }
+    }
+}
+
+/// Return the canonical form of the given path, or the path itself if it does not exist.
+fn canonical(path: &str) -> String {
+    std::fs::canonicalize(path)
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|_| path.to_string())
+}
+
+/// Return true if both paths refer to the same file.
+pub fn same_file(first: &str, second: &str) -> bool {
+    match (std::fs::canonicalize(first), std::fs::canonicalize(second)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => first == second,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::configloader::ConfigLoader;
+    use crate::config::configsource::{ConfigLayer, ConfigSource};
+    use crate::wsl::inv::invocablecategorylist::InvocableCategoryList;
+
+    #[test]
+    fn it_skips_broken_files() {
+        let path = std::env::temp_dir().join("wink-configloader-broken.json");
+        std::fs::write(&path, "{ \"categories\": [ { \"name\": \"Broken\", } ] }").unwrap();
+        let loader = ConfigLoader {
+            sources: vec![ConfigSource::new(ConfigLayer::User, &path.to_string_lossy())],
+            documents: vec![],
+        };
+
+        let mut category_list = InvocableCategoryList::built_in();
+        let count = category_list.categories.len();
+        let errors = loader.load(&mut category_list);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 1);
+        assert_eq!(category_list.categories.len(), count);
+        assert!(category_list.sources.is_empty());
+    }
+
+    #[test]
+    fn it_applies_includes_before_the_including_file() {
+        let dir = std::env::temp_dir().join("wink-configloader-includes");
+        std::fs::create_dir_all(dir.join("packs")).unwrap();
+        std::fs::write(
+            dir.join("wink.json"),
+            r#"{ "includes": [ "packs/*.json", { "path": "missing/*.json", "optional": true }, "missing.json" ], "categories": [ { "name": "Mine", "invocables": { "np": "notepad.exe" } } ] }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("packs").join("office.json"),
+            r#"{ "includes": [ "../wink.json" ], "categories": [ { "name": "Packs", "invocables": { "np": "notepad++.exe", "xl": "excel.exe" } } ] }"#,
+        )
+        .unwrap();
+        let path = dir.join("wink.json").to_string_lossy().to_string();
+        let loader = ConfigLoader {
+            sources: vec![ConfigSource::new(ConfigLayer::User, &path)],
+            documents: vec![],
+        };
+
+        let mut category_list = InvocableCategoryList::built_in();
+        let errors = loader.load(&mut category_list);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // the cycle back to wink.json and the missing file that is not optional
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].message.starts_with("include cycle "));
+        assert!(errors[1].path.ends_with("missing.json"));
+        assert_eq!(category_list.get_invocable("np").unwrap().command, "notepad.exe");
+        let xl = category_list.get_invocable("xl").unwrap();
+        assert!(xl.source.path.ends_with("office.json"));
+        assert_eq!(xl.source.included_by, path);
+        assert_eq!(xl.source.layer, ConfigLayer::User);
+    }
+
+    #[test]
+    fn it_reads_drop_in_files_in_lexical_order() {
+        let dir = std::env::temp_dir().join("wink-configloader-conf.d");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("20-team.toml"), "[[categories]]\nname = \"Team\"\n[categories.invocables]\nnp = \"notepad++.exe\"\n").unwrap();
+        std::fs::write(dir.join("10-base.json"), "{ \"categories\": [ { \"name\": \"Base\", \"invocables\": { \"np\": \"notepad.exe\" } } ] }").unwrap();
+        std::fs::write(dir.join("README.md"), "# not configuration").unwrap();
+
+        let sources = ConfigLoader::sources_at(&dir.to_string_lossy());
+        let mut category_list = InvocableCategoryList::built_in();
+        let errors = ConfigLoader {
+            sources: sources.clone(),
+            documents: vec![],
+        }
+        .load(&mut category_list);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(errors.is_empty());
+        assert_eq!(sources.len(), 2);
+        assert!(sources.iter().all(|s| s.layer == ConfigLayer::DropIn));
+        assert!(sources[0].path.ends_with("10-base.json"));
+        let invocable = category_list.get_invocable("np").unwrap();
+        assert_eq!(invocable.command, "notepad++.exe");
+        assert!(invocable.source.path.ends_with("20-team.toml"));
+        assert!(ConfigLoader::sources_at(&dir.to_string_lossy()).is_empty());
+    }
+}
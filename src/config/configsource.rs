@@ -0,0 +1,97 @@
+//! A ConfigSource records the configuration layer and file that defined an Invocable.
+
+/// Configuration layers, in the order that wink applies them.
+/// Each layer can replace invocables defined by the layers before it.
+#[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigLayer {
+    /// Compiled into wink.
+    #[default]
+    BuiltIn,
+
+    /// Shared by all users of the machine: /etc/wink/wink.json or %ProgramData%\wink\wink.json,
+    /// or wink.toml, wink.yaml, or wink.yml in the same directory.
+    System,
+
+    /// Each JSON, TOML, or YAML file in $XDG_CONFIG_HOME/wink/conf.d (WSL) or %APPDATA%\wink\conf.d (Windows),
+    /// in lexical order, such as command packs that teams share.
+    DropIn,
+
+    /// The current user: $HOME/.wink.json (WSL) or %USERPROFILE%\wink.json (Windows), or .toml, .yaml, or .yml.
+    User,
+
+    /// The nearest .wink.json, .wink.toml, .wink.yaml, or .wink.yml in the current directory or any of its parents.
+    Project,
+}
+
+impl std::fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ConfigLayer::BuiltIn => "built-in",
+            ConfigLayer::System => "system",
+            ConfigLayer::DropIn => "drop-in",
+            ConfigLayer::User => "user",
+            ConfigLayer::Project => "project",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+/// The configuration layer and file that defined an Invocable.
+#[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema, Debug, Clone, PartialEq, Eq, Default)]
+pub struct ConfigSource {
+    /// The configuration layer that defined the invocable.
+    pub layer: ConfigLayer,
+
+    /// The path to the configuration file, or empty for built-in invocables.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub path: String,
+
+    /// The path to the configuration file that included this one, or empty if wink found this file itself.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub included_by: String,
+}
+
+impl ConfigSource {
+    /// Return a ConfigSource for the given layer and file.
+    pub fn new(layer: ConfigLayer, path: &str) -> ConfigSource {
+        ConfigSource {
+            layer,
+            path: path.to_string(),
+            included_by: String::new(),
+        }
+    }
+
+    /// Return a ConfigSource in the same layer for a file that this file includes.
+    pub fn include(&self, path: &str) -> ConfigSource {
+        ConfigSource {
+            layer: self.layer,
+            path: path.to_string(),
+            included_by: self.path.clone(),
+        }
+    }
+
+    /// Return true if wink defines the invocable rather than a configuration file.
+    pub fn is_built_in(&self) -> bool {
+        self.layer == ConfigLayer::BuiltIn
+    }
+}
+
+/// Render as the layer followed by the path, such as user /home/jw/.wink.json,
+/// and the including file, such as user /home/jw/packs/office.json (included by /home/jw/.wink.json).
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.layer)?;
+        } else {
+            write!(f, "{0} {1}", self.layer, self.path)?;
+        }
+
+        if !self.included_by.is_empty() {
+            write!(f, " (included by {0})", self.included_by)?;
+        }
+
+        Ok(())
+    }
+}
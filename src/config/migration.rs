@@ -0,0 +1,159 @@
+//! The wink.config.migration module upgrades configuration documents written for earlier versions of wink
+//! to the current format_version before wink deserializes them.
+
+use crate::wsl::inv::launchmode::LaunchMode; // /src/wsl/inv/launchmode.rs
+
+/// The format_version of configuration documents that this version of wink writes.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// The format_version of documents without a format_version field.
+pub const UNVERSIONED_FORMAT_VERSION: u32 = 1;
+
+/// Launch flags that format_version 2 replaced with mode.
+const LAUNCH_FLAGS: [&str; 6] = [
+    "use_cmd",
+    "use_start",
+    "background",
+    "use_call",
+    "use_explorer",
+    "use_bash",
+];
+
+/// A function that upgrades a document from the previous format_version.
+type Migration = fn(&mut serde_json::Value);
+
+/// Each migration upgrades a document from the previous format_version to the version listed with it.
+const MIGRATIONS: [(u32, Migration); 1] = [(2, migrate_launch_flags)];
+
+/// Return the format_version of the given configuration document.
+pub fn document_version(document: &serde_json::Value) -> Result<u32, String> {
+    match document.get("format_version") {
+        None => Ok(UNVERSIONED_FORMAT_VERSION),
+        Some(value) => match value.as_u64() {
+            Some(version) if version >= UNVERSIONED_FORMAT_VERSION as u64 => Ok(version as u32),
+            _ => Err(format!("invalid format_version {0}", value)),
+        },
+    }
+}
+
+/// Upgrade the given configuration document to the current format_version in place
+/// and return the format_version of the document before the upgrade.
+pub fn migrate(document: &mut serde_json::Value) -> Result<u32, String> {
+    let version = document_version(document)?;
+
+    if version > FORMAT_VERSION {
+        return Err(format!(
+            "format_version {0} is newer than format_version {1} supported by this version of wink",
+            version, FORMAT_VERSION
+        ));
+    }
+
+    for (target, migration) in MIGRATIONS.iter() {
+        if version < *target {
+            migration(document);
+        }
+    }
+
+    if let Some(map) = document.as_object_mut() {
+        let mut upgraded = serde_json::Map::new();
+        upgraded.insert("format_version".to_string(), serde_json::json!(FORMAT_VERSION));
+
+        for (key, value) in std::mem::take(map) {
+            if key != "format_version" {
+                upgraded.insert(key, value);
+            }
+        }
+
+        *map = upgraded;
+    }
+
+    Ok(version)
+}
+
+/// Return the invocable objects in the given configuration document,
+/// from categories that list invocables or that map command codes to them.
+fn invocables_mut(document: &mut serde_json::Value) -> Vec<&mut serde_json::Map<String, serde_json::Value>> {
+    let mut result = vec![];
+
+    if let Some(categories) = document.get_mut("categories").and_then(|c| c.as_array_mut()) {
+        for category in categories.iter_mut() {
+            match category.get_mut("invocables") {
+                Some(serde_json::Value::Array(list)) => result.extend(list.iter_mut().filter_map(|i| i.as_object_mut())),
+                Some(serde_json::Value::Object(map)) => result.extend(map.values_mut().filter_map(|i| i.as_object_mut())),
+                _ => {}
+            }
+        }
+    }
+
+    result
+}
+
+/// Version 2: replace the use_cmd, use_start, background, use_call, use_explorer, and use_bash flags with mode.
+/// Leave invocables with conflicting flags unchanged so that deserialization reports the conflict.
+fn migrate_launch_flags(document: &mut serde_json::Value) {
+    for invocable in invocables_mut(document) {
+        if invocable.contains_key("mode") || !LAUNCH_FLAGS.iter().any(|f| invocable.contains_key(*f)) {
+            continue;
+        }
+
+        let flag = |name: &str| invocable.get(name).and_then(|v| v.as_bool()).unwrap_or(false);
+
+        if let Ok(mode) = LaunchMode::from_flags(
+            flag("use_cmd"),
+            flag("use_start"),
+            flag("background"),
+            flag("use_call"),
+            flag("use_explorer"),
+            flag("use_bash"),
+        ) {
+            let mut upgraded = serde_json::Map::new();
+
+            // put mode where the first flag was to preserve the order of the other fields
+            for (key, value) in std::mem::take(invocable) {
+                if LAUNCH_FLAGS.contains(&key.as_str()) {
+                    if !mode.is_direct() && !upgraded.contains_key("mode") {
+                        upgraded.insert("mode".to_string(), serde_json::to_value(mode).unwrap());
+                    }
+                } else {
+                    upgraded.insert(key, value);
+                }
+            }
+
+            *invocable = upgraded;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::migration::{migrate, FORMAT_VERSION};
+
+    #[test]
+    fn it_migrates_launch_flags() {
+        let mut document = serde_json::json!({ "categories": [
+            { "name": "Mine", "invocables": [
+                { "command_code": "dir", "description": "List", "command": "dir", "use_cmd": true, "use_bash": false, "arguments": [ "/w" ] },
+                { "command_code": "word", "command": "WINWORD.EXE", "use_cmd": false } ] },
+            { "name": "Short", "invocables": { "pc": { "command": "shell:MyComputerFolder", "use_explorer": true } } } ] });
+
+        assert_eq!(migrate(&mut document), Ok(1));
+        assert_eq!(
+            serde_json::to_string(&document).unwrap(),
+            serde_json::to_string(&serde_json::json!({ "format_version": FORMAT_VERSION, "categories": [
+                { "name": "Mine", "invocables": [
+                    { "command_code": "dir", "description": "List", "command": "dir", "mode": "cmd", "arguments": [ "/w" ] },
+                    { "command_code": "word", "command": "WINWORD.EXE" } ] },
+                { "name": "Short", "invocables": { "pc": { "command": "shell:MyComputerFolder", "mode": "explorer" } } } ] }))
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn it_rejects_newer_versions() {
+        let mut document = serde_json::json!({ "format_version": FORMAT_VERSION + 1, "categories": [] });
+        assert!(migrate(&mut document).is_err());
+
+        let mut document = serde_json::json!({ "format_version": FORMAT_VERSION, "categories": [] });
+        assert_eq!(migrate(&mut document), Ok(FORMAT_VERSION));
+    }
+}
@@ -0,0 +1,62 @@
+//! The wink.config.schema module generates a JSON Schema for configuration files
+//! from the doc comments on InvocableCategoryList, InvocableCategory, and Invocable,
+//! for editors such as VS Code to validate and complete wink.json.
+
+use crate::wsl::inv::invocablecategorylist::InvocableCategoryList; // /src/wsl/inv/invocablecategorylist.rs
+use crate::wsl::inv::tokens::TOKENS; // /src/wsl/inv/tokens.rs
+
+/// Return the JSON Schema for wink configuration files.
+pub fn json_schema() -> serde_json::Value {
+    let mut schema = schemars::schema_for!(InvocableCategoryList).to_value();
+
+    if let Some(command) = schema.pointer_mut("/$defs/Invocable/properties/command") {
+        let tokens: Vec<String> = TOKENS
+            .iter()
+            .map(|(name, description)| format!("{0} for {1}", name, description))
+            .collect();
+        let description = format!(
+            "{0} wink replaces these tokens: {1}.",
+            command["description"].as_str().unwrap_or_default(),
+            tokens.join("; ")
+        );
+        command["description"] = serde_json::json!(description);
+        command["examples"] = serde_json::json!([
+            "$pf64/Microsoft Office/root/Office16/WINWORD.EXE",
+            "$userpath/AppData/Local/Programs/Microsoft VS Code/Code.exe",
+            "$sysliveprocmon64.exe",
+            "shell:Desktop"
+        ]);
+    }
+
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_validates_the_exported_configuration() {
+        let schema = super::json_schema();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+
+        // the baseline export, upgraded to the current format, and the export of the built-in catalog
+        let mut baseline: serde_json::Value = serde_json::from_str(include_str!("../../tests/fixtures/baseline-wink.json")).unwrap();
+        crate::config::migration::migrate(&mut baseline).unwrap();
+        let built_in = serde_json::to_value(crate::wsl::inv::invocablecategorylist::InvocableCategoryList::built_in()).unwrap();
+
+        for exported in [baseline, built_in] {
+            let errors: Vec<String> = validator.iter_errors(&exported).map(|e| e.to_string()).collect();
+            assert!(errors.is_empty(), "{0:?}", errors);
+        }
+    }
+
+    #[test]
+    fn it_rejects_unknown_fields() {
+        let schema = super::json_schema();
+        let validator = jsonschema::validator_for(&schema).unwrap();
+        assert!(validator.is_valid(&serde_json::json!({ "categories": [ { "name": "Mine", "invocables": { "word": "WINWORD.EXE" } } ] })));
+        assert!(validator.is_valid(&serde_json::json!({ "categories": [ { "name": "Mine", "invocables": [ { "command_code": "dir", "command": "dir", "mode": { "cmd": { "wait": false } } } ] } ] })));
+        assert!(!validator.is_valid(&serde_json::json!({ "categories": [ { "name": "Mine", "invocables": [ { "command_code": "x", "use_shell": true } ] } ] })));
+        assert!(!validator.is_valid(&serde_json::json!({ "categories": [ { "name": "Mine", "invocables": [ { "command": "x" } ] } ] })));
+        assert!(!validator.is_valid(&serde_json::json!({ "categories": [ { "name": "Mine", "invocables": [ { "command_code": "x", "mode": "shell" } ] } ] })));
+    }
+}
@@ -0,0 +1,5 @@
+//! The wink.docs module renders the merged list of categories and invocables, including those from
+//! configuration files, as documentation: Markdown, HTML, CSV, or a man page.
+
+pub mod docformat; // /src/docs/docformat.rs defines docs::docformat::DocFormat
+pub mod docscommand; // /src/docs/docscommand.rs defines the wink docs command
@@ -0,0 +1,355 @@
+//! A DocFormat identifies a documentation format for the wink docs command, Markdown, HTML, CSV, or man,
+//! and renders the categories and invocables of an InvocableCategoryList in that format.
+
+use crate::wsl::inv::invocable::Invocable; // /src/wsl/inv/invocable.rs
+use crate::wsl::inv::invocablecategory::InvocableCategory; // /src/wsl/inv/invocablecategory.rs
+use crate::wsl::inv::invocablecategorylist::InvocableCategoryList; // /src/wsl/inv/invocablecategorylist.rs
+
+/// The names of the formats for the wink docs command.
+pub const DOC_FORMAT_NAMES: [&str; 4] = ["markdown", "html", "csv", "man"];
+
+/// The columns of the Markdown and HTML tables.
+const COLUMNS: [&str; 8] = ["Code", "Aliases", "Description", "Command", "Mode", "Arguments", "Tags", "Source"];
+
+/// The header of CSV output.
+const CSV_HEADER: &str = "category,code,aliases,description,command,mode,arguments,argument_kind,tags,keywords,layer,path";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocFormat {
+    Markdown,
+    Html,
+    Csv,
+    Man,
+}
+
+impl DocFormat {
+    /// Return the format with the given name, ignoring case, such as md for markdown, if any.
+    pub fn from_name(name: &str) -> Option<DocFormat> {
+        match name.to_lowercase().as_str() {
+            "markdown" | "md" => Some(DocFormat::Markdown),
+            "html" | "htm" => Some(DocFormat::Html),
+            "csv" => Some(DocFormat::Csv),
+            "man" | "roff" => Some(DocFormat::Man),
+            _ => None,
+        }
+    }
+
+    /// Return the documentation for the command with the given name, such as wink, in this format:
+    /// a Markdown table for each category, a standalone HTML page with anchors for each category and command code,
+    /// a CSV row for each invocable, or a roff man page. Categories appear in the order of the list,
+    /// and invocables in the order of their command codes.
+    pub fn render(&self, name: &str, category_list: &InvocableCategoryList) -> String {
+        let categories = sorted(category_list);
+
+        match self {
+            DocFormat::Markdown => markdown(name, &categories),
+            DocFormat::Html => html(name, &categories),
+            DocFormat::Csv => csv(&categories),
+            DocFormat::Man => man(name, &categories),
+        }
+    }
+}
+
+/// Return the categories that contain invocables, each with its invocables sorted by command code.
+fn sorted(category_list: &InvocableCategoryList) -> Vec<(&InvocableCategory, Vec<&Invocable>)> {
+    category_list
+        .categories
+        .iter()
+        .filter(|category| !category.invocables.is_empty())
+        .map(|category| {
+            let mut invocables: Vec<&Invocable> = category.invocables.iter().collect();
+            invocables.sort();
+            (category, invocables)
+        })
+        .collect()
+}
+
+/// Return the anchor for the text, which matches the anchors that GitHub generates for Markdown headings,
+/// such as ease-of-access for Ease of Access.
+fn slug(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_alphanumeric() || c == '_' => Some(c),
+            ' ' | '-' => Some('-'),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Return the layer that defined the invocable, and the configuration file for invocables that are not built in.
+fn source(invocable: &Invocable) -> String {
+    if invocable.source.is_built_in() {
+        invocable.source.layer.to_string()
+    } else {
+        format!("{0} ({1})", invocable.source.layer, invocable.source.path)
+    }
+}
+
+/// Return the values of the table columns for the invocable.
+fn cells(invocable: &Invocable) -> [String; 8] {
+    [
+        invocable.command_code.clone(),
+        invocable.aliases.join(", "),
+        invocable.description.clone(),
+        invocable.command.clone(),
+        invocable.mode.to_string(),
+        invocable.arguments.join(" "),
+        invocable.tags.join(", "),
+        source(invocable),
+    ]
+}
+
+/// Return the text escaped for a cell of a Markdown table, where < and > would otherwise start HTML tags.
+fn markdown_escape(text: &str) -> String {
+    text.replace('\\', r"\\")
+        .replace('|', r"\|")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Return the Markdown document, with a list of links to the categories followed by a table for each category.
+fn markdown(name: &str, categories: &[(&InvocableCategory, Vec<&Invocable>)]) -> String {
+    let mut doc = format!("# {0} command codes\n\n", name);
+
+    for (category, invocables) in categories.iter() {
+        doc.push_str(&format!("- [{0}](#{1}) ({2})\n", markdown_escape(&category.name), slug(&category.name), invocables.len()));
+    }
+
+    for (category, invocables) in categories.iter() {
+        doc.push_str(&format!("\n## {0}\n\n| {1} |\n|", markdown_escape(&category.name), COLUMNS.join(" | ")));
+        doc.push_str(&" --- |".repeat(COLUMNS.len()));
+        doc.push('\n');
+
+        for invocable in invocables.iter() {
+            let cells: Vec<String> = cells(invocable).iter().map(|c| markdown_escape(c)).collect();
+            doc.push_str(&format!("| `{0}` | {1} |\n", cells[0], cells[1..].join(" | ")));
+        }
+    }
+
+    doc
+}
+
+/// Return the text escaped for HTML content and attribute values.
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Return the standalone HTML page, with a list of links to the categories followed by a table for each category.
+/// Each category and each command code has an anchor, such as #privacy and #code-word.
+fn html(name: &str, categories: &[(&InvocableCategory, Vec<&Invocable>)]) -> String {
+    let title = html_escape(&format!("{0} command codes", name));
+    let mut doc = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{0}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; margin-bottom: 2em; }}
+th, td {{ border: 1px solid #ccc; padding: 0.25em 0.5em; text-align: left; vertical-align: top; }}
+tr:target {{ background: #ffd; }}
+</style>
+</head>
+<body>
+<h1>{0}</h1>
+<ul>
+"#,
+        title
+    );
+
+    for (category, invocables) in categories.iter() {
+        doc.push_str(&format!(
+            "<li><a href=\"#{0}\">{1}</a> ({2})</li>\n",
+            slug(&category.name),
+            html_escape(&category.name),
+            invocables.len()
+        ));
+    }
+
+    doc.push_str("</ul>\n");
+
+    for (category, invocables) in categories.iter() {
+        let id = slug(&category.name);
+        doc.push_str(&format!(
+            "<h2 id=\"{0}\"><a href=\"#{0}\">{1}</a></h2>\n<table>\n<thead><tr>",
+            id,
+            html_escape(&category.name)
+        ));
+
+        for column in COLUMNS.iter() {
+            doc.push_str(&format!("<th>{}</th>", column));
+        }
+
+        doc.push_str("</tr></thead>\n<tbody>\n");
+
+        for invocable in invocables.iter() {
+            let cells: Vec<String> = cells(invocable).iter().map(|c| html_escape(c)).collect();
+            let id = format!("code-{}", slug(&invocable.command_code));
+            doc.push_str(&format!("<tr id=\"{0}\"><td><a href=\"#{0}\"><code>{1}</code></a></td>", id, cells[0]));
+
+            for cell in cells[1..].iter() {
+                doc.push_str(&format!("<td>{}</td>", cell));
+            }
+
+            doc.push_str("</tr>\n");
+        }
+
+        doc.push_str("</tbody>\n</table>\n");
+    }
+
+    doc.push_str("</body>\n</html>\n");
+    doc
+}
+
+/// Return the text quoted for a CSV field if it contains a comma, a double quote, or a line break.
+fn csv_quote(text: &str) -> String {
+    if text.contains([',', '"', '\r', '\n']) {
+        format!("\"{0}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Return the CSV document, with a header and a row for each invocable.
+/// Lists such as aliases and tags separate their values with spaces.
+fn csv(categories: &[(&InvocableCategory, Vec<&Invocable>)]) -> String {
+    let mut doc = format!("{}\r\n", CSV_HEADER);
+
+    for (category, invocables) in categories.iter() {
+        for invocable in invocables.iter() {
+            let argument_kind = if invocable.argument_kind.is_none() {
+                String::new()
+            } else {
+                invocable.argument_kind.to_string()
+            };
+
+            let fields = [
+                category.name.clone(),
+                invocable.command_code.clone(),
+                invocable.aliases.join(" "),
+                invocable.description.clone(),
+                invocable.command.clone(),
+                invocable.mode.to_string(),
+                invocable.arguments.join(" "),
+                argument_kind,
+                invocable.tags.join(" "),
+                invocable.keywords.clone(),
+                invocable.source.layer.to_string(),
+                invocable.source.path.clone(),
+            ];
+
+            let fields: Vec<String> = fields.iter().map(|f| csv_quote(f)).collect();
+            doc.push_str(&fields.join(","));
+            doc.push_str("\r\n");
+        }
+    }
+
+    doc
+}
+
+/// Return the text escaped for roff, so that backslashes, hyphens, and leading dots and quotes appear literally.
+fn roff_escape(text: &str) -> String {
+    let escaped = text.replace('\\', r"\e").replace('-', r"\-").replace(['\r', '\n'], " ");
+
+    if escaped.starts_with(['.', '\'']) {
+        format!(r"\&{}", escaped)
+    } else {
+        escaped
+    }
+}
+
+/// Return the man page, with the synopsis, the options, and a section for each category.
+fn man(name: &str, categories: &[(&InvocableCategory, Vec<&Invocable>)]) -> String {
+    let mut doc = format!(
+        r#".TH {0} 1 "" "{1} {2}" "User Commands"
+.SH NAME
+{1} \- access Linux and Windows applications and features by command code
+.SH SYNOPSIS
+.B {1}
+[\fIoptions\fR] \fIcommand_code\fR [\fIarguments\fR ...]
+.SH DESCRIPTION
+{1} invokes the command for the command code with the arguments that follow it.
+Command codes ignore case, and can be abbreviated to any prefix that no other command code or alias starts with.
+.SH OPTIONS
+"#,
+        roff_escape(&name.to_uppercase()),
+        roff_escape(name),
+        env!("CARGO_PKG_VERSION")
+    );
+
+    for (option, description) in crate::winkconfig::OPTIONS.iter() {
+        doc.push_str(&format!(".TP\n.B {0}\n{1}\n", roff_escape(option), roff_escape(description)));
+    }
+
+    doc.push_str(".SH COMMAND CODES\n");
+
+    for (category, invocables) in categories.iter() {
+        doc.push_str(&format!(".SS {}\n", roff_escape(&category.name)));
+
+        for invocable in invocables.iter() {
+            let description = if invocable.description.is_empty() {
+                &invocable.command
+            } else {
+                &invocable.description
+            };
+
+            doc.push_str(&format!(".TP\n.B {0}\n{1}\n", roff_escape(&invocable.command_code), roff_escape(description)));
+
+            if !invocable.aliases.is_empty() {
+                doc.push_str(&format!(".br\nAliases: {}\n", roff_escape(&invocable.aliases.join(", "))));
+            }
+
+            if !invocable.source.is_built_in() {
+                doc.push_str(&format!(".br\nSource: {}\n", roff_escape(&source(invocable))));
+            }
+        }
+    }
+
+    doc
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::configsource::{ConfigLayer, ConfigSource};
+    use crate::docs::docformat::DocFormat;
+    use crate::wsl::inv::invocable::Invocable;
+    use crate::wsl::inv::invocablecategory::InvocableCategory;
+    use crate::wsl::inv::invocablecategorylist::InvocableCategoryList;
+
+    #[test]
+    fn it_renders_user_categories_in_every_format() {
+        let mut category = InvocableCategory::new("Team Tools");
+        let mut invocable = Invocable::bin("deploy", "deploy.exe", "Deploy <app>, \"quickly\" | safely");
+        invocable.aliases = vec!["-dp".to_string()];
+        invocable.source = ConfigSource::new(ConfigLayer::User, "/home/jw/.wink.json");
+        category.add(invocable);
+        let category_list = InvocableCategoryList { categories: vec![category], ..InvocableCategoryList::built_in() };
+
+        let markdown = DocFormat::Markdown.render("wink", &category_list);
+        assert!(markdown.contains("- [Team Tools](#team-tools) (1)"));
+        assert!(markdown.contains("| `deploy` | -dp | Deploy &lt;app&gt;, \"quickly\" \\| safely | deploy.exe |"));
+
+        let html = DocFormat::Html.render("wink", &category_list);
+        assert!(html.contains("<h2 id=\"team-tools\"><a href=\"#team-tools\">Team Tools</a></h2>"));
+        assert!(html.contains("<tr id=\"code-deploy\"><td><a href=\"#code-deploy\"><code>deploy</code></a></td>"));
+        assert!(html.contains("Deploy &lt;app&gt;, &quot;quickly&quot; | safely"));
+
+        let csv = DocFormat::Csv.render("wink", &category_list);
+        assert!(csv.contains("Team Tools,deploy,-dp,\"Deploy <app>, \"\"quickly\"\" | safely\",deploy.exe,direct,,,,,user,/home/jw/.wink.json\r\n"));
+
+        let man = DocFormat::Man.render("wink", &category_list);
+        assert!(man.starts_with(".TH WINK 1"));
+        assert!(man.contains(".SS Team Tools\n.TP\n.B deploy\n"));
+        assert!(man.contains("Aliases: \\-dp\n"));
+
+        assert_eq!(DocFormat::from_name("MD"), Some(DocFormat::Markdown));
+        assert_eq!(DocFormat::from_name("pdf"), None);
+    }
+}
@@ -0,0 +1,33 @@
+//! The wink docs command writes documentation for the command codes that wink loads to stdout,
+//! such as wink docs markdown > COMMANDS.md or wink docs man > ~/.local/share/man/man1/wink.1.
+
+use crate::docs::docformat::{DocFormat, DOC_FORMAT_NAMES}; // /src/docs/docformat.rs
+use crate::winkconfig::WinkConfig; // /src/winkconfig.rs
+use crate::wsl::inv::invocablecategorylist::InvocableCategoryList; // /src/wsl/inv/invocablecategorylist.rs
+use std::io::Write;
+
+/// The command code that selects the wink docs command.
+pub const DOCS_COMMAND_CODE: &str = "docs";
+
+/// Write the documentation in the format named after docs to stdout
+/// and return the exit code for the process.
+pub fn run(config: &WinkConfig, category_list: &InvocableCategoryList) -> i32 {
+    let format = match config.cmd_args.as_slice() {
+        [name] => DocFormat::from_name(name),
+        _ => None,
+    };
+
+    match format {
+        Some(format) => {
+            let doc = format.render(config.cmd_name.trim_end_matches(".exe"), category_list);
+            crate::output::Output::stdout(config.color).run(|out| {
+                write!(out, "{}", doc)?;
+                Ok(0)
+            })
+        }
+        None => {
+            eprintln!("{0} docs {1}", config.cmd_name, DOC_FORMAT_NAMES.join("|"));
+            1
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//! The wink.doctor module checks the invocables that wink loads for problems,
+//! such as commands that do not exist on this machine.
+
+pub mod doctorcommand; // /src/doctor/doctorcommand.rs defines the wink doctor command
+pub mod doctorfinding; // /src/doctor/doctorfinding.rs defines doctor::doctorfinding::DoctorFinding
+pub mod doctorreport; // /src/doctor/doctorreport.rs defines doctor::doctorreport::DoctorReport
@@ -0,0 +1,65 @@
+//! The wink doctor command checks the invocables that wink loads and the configuration files that define them,
+//! such as wink doctor --json word excel.
+
+use crate::doctor::doctorfinding::Severity; // /src/doctor/doctorfinding.rs
+use crate::doctor::doctorreport::DoctorReport; // /src/doctor/doctorreport.rs
+use crate::winkconfig::WinkConfig; // /src/winkconfig.rs
+use crate::wsl::inv::invocablecategorylist::InvocableCategoryList; // /src/wsl/inv/invocablecategorylist.rs
+use crate::wsl::inv::tokenvalues::TokenValues; // /src/wsl/inv/tokenvalues.rs
+
+/// The command code that selects the wink doctor command.
+pub const DOCTOR_COMMAND_CODE: &str = "doctor";
+
+/// Check the invocables named after doctor, or all invocables if none, write the report to stdout,
+/// and return the exit code for the process, which is 1 if the report contains errors.
+pub fn run(config: &WinkConfig, category_list: &InvocableCategoryList) -> i32 {
+    let mut json = false;
+    let mut verbose = config.verbose;
+    let mut codes: Vec<String> = vec![];
+
+    for arg in config.cmd_args.iter() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-v" | "--verbose" => verbose = true,
+            _ if arg.starts_with('-') => {
+                eprintln!("Unrecognized doctor option: {0}", arg);
+                eprintln!("{0} doctor [--json] [-v] [code]...", config.cmd_name);
+                return 1;
+            }
+            _ => codes.push(arg.clone()),
+        }
+    }
+
+    // check the invocables that the command codes select the same way that wink invokes them, such as by prefix
+    for code in codes.iter_mut() {
+        match category_list.find_invocable(code) {
+            Some(invocable) => *code = invocable.command_code.clone(),
+            None => {
+                eprintln!("Command not recognized: {0}", code);
+                return 1;
+            }
+        }
+    }
+
+    let report = DoctorReport::check(category_list, &codes, &TokenValues::get());
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        // -v includes the findings about commands that wink doctor could not check
+        for finding in report.findings.iter().filter(|f| verbose || f.severity > Severity::Info) {
+            println!("{}", finding);
+        }
+
+        println!(
+            "{0} invocables checked: {1} errors, {2} warnings",
+            report.checked, report.errors, report.warnings
+        );
+    }
+
+    if report.is_healthy() {
+        0
+    } else {
+        1
+    }
+}
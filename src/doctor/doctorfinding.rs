@@ -0,0 +1,73 @@
+//! A DoctorFinding describes a problem that wink doctor found with an invocable or a configuration file.
+
+use crate::config::configsource::ConfigSource; // /src/config/configsource.rs
+
+/// How serious a finding is. Errors cause wink doctor to exit with a non-zero exit code.
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Something that wink doctor could not check, such as a network path.
+    Info,
+
+    /// Something that might not work, such as a command that is not on the PATH.
+    Warning,
+
+    /// Something that does not work, such as an executable that does not exist.
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+
+        // pad so that the names line up in reports
+        match f.width() {
+            Some(width) => write!(f, "{0:>1$}", name, width),
+            None => write!(f, "{}", name),
+        }
+    }
+}
+
+#[derive(serde::Serialize, Debug, Clone)]
+pub struct DoctorFinding {
+    /// How serious the finding is.
+    pub severity: Severity,
+
+    /// The command code of the invocable, or empty for findings about configuration files or programs.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub command_code: String,
+
+    /// The category of the invocable, or empty for findings about configuration files or programs.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub category: String,
+
+    /// A description of the finding.
+    pub message: String,
+
+    /// The configuration layer and file that defined the invocable.
+    #[serde(skip_serializing_if = "ConfigSource::is_built_in")]
+    pub source: ConfigSource,
+}
+
+/// Render as severity : code : category : message [source], omitting the parts that are empty.
+impl std::fmt::Display for DoctorFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:>7} : ", self.severity)?;
+
+        if !self.command_code.is_empty() {
+            write!(f, "{0} : {1} : ", self.command_code, self.category)?;
+        }
+
+        write!(f, "{}", self.message)?;
+
+        if !self.source.is_built_in() {
+            write!(f, " [{}]", self.source)?;
+        }
+
+        Ok(())
+    }
+}
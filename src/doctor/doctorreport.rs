@@ -0,0 +1,250 @@
+//! A DoctorReport contains the findings of wink doctor about the invocables that wink loads:
+//! commands that do not exist, missing descriptions, command codes in more than one category,
+//! command codes reserved for the commands that wink runs itself, and configuration files that wink could not load, such as files with conflicting launch flags.
+
+use crate::doctor::doctorfinding::{DoctorFinding, Severity}; // /src/doctor/doctorfinding.rs
+use crate::wsl::inv::invocable::Invocable; // /src/wsl/inv/invocable.rs
+use crate::wsl::inv::invocablecategorylist::InvocableCategoryList; // /src/wsl/inv/invocablecategorylist.rs
+use crate::wsl::inv::invoker::Invoker; // /src/wsl/inv/invoker.rs
+use crate::wsl::inv::launchmode::LaunchMode; // /src/wsl/inv/launchmode.rs
+use crate::wsl::inv::tokenvalues::TokenValues; // /src/wsl/inv/tokenvalues.rs
+
+/// Extensions that Windows tries for commands without an extension.
+const EXECUTABLE_EXTENSIONS: [&str; 4] = [".exe", ".com", ".bat", ".cmd"];
+
+/// Matches commands that are URIs, such as ms-settings:display, compiled once for all invocables.
+static URI: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+
+#[derive(serde::Serialize, Debug, Default)]
+pub struct DoctorReport {
+    /// The number of invocables checked.
+    pub checked: usize,
+
+    /// The number of findings with error severity.
+    pub errors: usize,
+
+    /// The number of findings with warning severity.
+    pub warnings: usize,
+
+    /// The findings, in the order of the invocables in the list.
+    pub findings: Vec<DoctorFinding>,
+}
+
+impl DoctorReport {
+    /// Return a report about the invocables with the given command codes in the given list, or all of them if none,
+    /// resolving their commands with the given token values the same way that Invoker does.
+    pub fn check(category_list: &InvocableCategoryList, codes: &[String], tokens: &TokenValues) -> DoctorReport {
+        let mut report = DoctorReport::default();
+        let mut defined: Vec<(&str, &str)> = vec![];
+        let mut programs: Vec<&str> = vec![];
+
+        for error in category_list.errors.iter() {
+            report.add(Severity::Error, None, "", error.to_string());
+        }
+
+        for category in category_list.categories.iter() {
+            for invocable in category.invocables.iter() {
+                if !codes.is_empty() && !codes.iter().any(|c| invocable.has_code(c)) {
+                    continue;
+                }
+
+                report.checked += 1;
+                let finding = Some(invocable);
+
+                for code in invocable.codes() {
+                    match defined.iter().find(|(c, _name)| c.eq_ignore_ascii_case(code)) {
+                        Some((_code, name)) => report.add(
+                            Severity::Error,
+                            finding,
+                            &category.name,
+                            format!("command code {0} also defined in category {1}", code, name),
+                        ),
+                        None => defined.push((code, &category.name)),
+                    }
+
+                    if crate::is_reserved(code) {
+                        report.add(
+                            Severity::Warning,
+                            finding,
+                            &category.name,
+                            format!("command code {0} is reserved for wink {0} and cannot be invoked", code),
+                        );
+                    }
+                }
+
+                if invocable.description.trim().is_empty() {
+                    report.add(Severity::Warning, finding, &category.name, "no description".to_string());
+                }
+
+                if let Some((severity, message)) = check_command(invocable, tokens) {
+                    report.add(severity, finding, &category.name, message);
+                }
+
+                if let Some(program) = invocable.mode.program() {
+                    if !programs.contains(&program) {
+                        programs.push(program);
+                    }
+                }
+            }
+        }
+
+        for program in programs {
+            if find_on_path(program).is_none() {
+                report.add(Severity::Error, None, "", format!("{0} not found on PATH", program));
+            }
+        }
+
+        report
+    }
+
+    /// Return true if the report contains no errors.
+    pub fn is_healthy(&self) -> bool {
+        self.errors == 0
+    }
+
+    fn add(&mut self, severity: Severity, invocable: Option<&Invocable>, category: &str, message: String) {
+        match severity {
+            Severity::Error => self.errors += 1,
+            Severity::Warning => self.warnings += 1,
+            Severity::Info => {}
+        }
+
+        self.findings.push(DoctorFinding {
+            severity,
+            command_code: invocable.map(|i| i.command_code.clone()).unwrap_or_default(),
+            category: category.to_string(),
+            message,
+            source: invocable.map(|i| i.source.clone()).unwrap_or_default(),
+        });
+    }
+}
+
+/// Return the severity and description of a problem with the command of the given invocable, if any.
+fn check_command(invocable: &Invocable, tokens: &TokenValues) -> Option<(Severity, String)> {
+    let command = invocable.command.trim();
+
+    if command.is_empty() {
+        // cmd.exe, explorer.exe, and bash.exe run the arguments, such as wink cmd echo %PATH%
+        return match invocable.mode.is_direct() {
+            true => Some((Severity::Error, "no command".to_string())),
+            false => None,
+        };
+    }
+
+    let unresolved = tokens.unresolved(command);
+
+    if !unresolved.is_empty() {
+        return Some((
+            Severity::Info,
+            format!("not checked because {0} could not be determined", unresolved.join(" and ")),
+        ));
+    }
+
+    let replaced = tokens.replace(command);
+
+    // URIs such as ms-settings:display and shell:MyComputerFolder are not files
+    if URI.get_or_init(|| regex::Regex::new(r"^[A-Za-z][A-Za-z0-9+.\-]+:").unwrap()).is_match(&replaced) {
+        return None;
+    }
+
+    if replaced.starts_with("\\\\") {
+        return Some((Severity::Info, format!("network path not checked: {0}", replaced)));
+    }
+
+    // bash.exe runs a command line, which starts with the program
+    let executable = match invocable.mode {
+        LaunchMode::Bash => replaced.split_whitespace().next().unwrap_or_default().to_string(),
+        _ => Invoker::resolve_command(invocable, tokens),
+    };
+
+    if executable.contains('/') || executable.contains('\\') {
+        if std::path::Path::new(&executable).exists() {
+            None
+        } else {
+            Some((Severity::Error, format!("not found: {0}", executable)))
+        }
+    } else if find_on_path(&executable).is_some() {
+        None
+    } else if invocable.mode.uses_cmd() && !executable.contains('.') {
+        Some((Severity::Info, format!("{0} not found on PATH, but could be a cmd.exe command", executable)))
+    } else {
+        Some((Severity::Warning, format!("{0} not found on PATH", executable)))
+    }
+}
+
+/// Return the path to the given program in the directories of the PATH environment variable, if any,
+/// trying the extensions that Windows tries for programs without an extension.
+fn find_on_path(program: &str) -> Option<std::path::PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    let has_extension = std::path::Path::new(program).extension().is_some();
+
+    for dir in std::env::split_paths(&path) {
+        let candidate = dir.join(program);
+
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+
+        if !has_extension {
+            for extension in EXECUTABLE_EXTENSIONS.iter() {
+                let candidate = dir.join(format!("{0}{1}", program, extension));
+
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::configerror::{ConfigError, ConfigErrorKind};
+    use crate::doctor::doctorfinding::Severity;
+    use crate::doctor::doctorreport::DoctorReport;
+    use crate::wsl::inv::invocable::Invocable;
+    use crate::wsl::inv::invocablecategory::InvocableCategory;
+    use crate::wsl::inv::invocablecategorylist::InvocableCategoryList;
+    use crate::wsl::inv::tokenvalues::TokenValues;
+
+    #[test]
+    fn it_reports_problems() {
+        let mut category_list = InvocableCategoryList::built_in();
+        category_list.categories.clear();
+        let mut first = InvocableCategory::new("First");
+        first.add(Invocable::sh("true", "/bin/true", "True"));
+        first.add(Invocable::sh("missing", "/nonexistent/bin/missing --flag", "Missing"));
+        first.add(Invocable::exp("pc", "shell:MyComputerFolder", ""));
+        first.add(Invocable::bin("rider", "$pf64/JetBrains/rider64.exe", "Rider"));
+        first.add(Invocable::sh("search", "/bin/true", "Search"));
+        let mut second = InvocableCategory::new("Second");
+        second.add(Invocable::sh("true", "/bin/true", "True again"));
+        category_list.categories = vec![first, second];
+        category_list.errors.push(ConfigError::new(
+            ConfigErrorKind::InvalidValue,
+            "/home/jw/.wink.json",
+            "conflicting launch flags: use_explorer, use_bash".to_string(),
+        ));
+
+        let tokens = TokenValues::default();
+        let report = DoctorReport::check(&category_list, &[], &tokens);
+        let find = |code: &str| report.findings.iter().find(|f| f.command_code == code).unwrap();
+
+        assert_eq!(report.checked, 6);
+        assert!(!report.is_healthy());
+        assert!(report.findings[0].message.contains("conflicting launch flags"));
+        assert_eq!(find("missing").severity, Severity::Error);
+        assert!(find("missing").message.ends_with("/nonexistent/bin/missing"));
+        assert_eq!(find("pc").message, "no description");
+        assert_eq!(find("rider").severity, Severity::Info);
+        assert_eq!(find("search").severity, Severity::Warning);
+        assert_eq!(find("search").message, "command code search is reserved for wink search and cannot be invoked");
+        assert!(report.findings.iter().any(|f| f.command_code == "true" && f.category == "Second"));
+
+        let report = DoctorReport::check(&category_list, &["pc".to_string()], &tokens);
+        assert_eq!(report.checked, 1);
+        assert_eq!(report.warnings, 1);
+    }
+}
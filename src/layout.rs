@@ -0,0 +1,184 @@
+//! The wink.layout module fits the command code tables of help to the width of the terminal:
+//! narrow terminals wrap descriptions under themselves, and wide terminals show several columns of command codes.
+
+use crate::output::Output; // /src/output.rs
+use std::io::Write;
+
+/// The most columns of command codes that a table shows, however wide the terminal.
+pub const MAX_COLUMNS: usize = 3;
+
+/// The spaces between columns of command codes.
+const GAP: &str = "   ";
+
+/// The fewest columns that wrapped descriptions get, even if command codes leave less room.
+const MIN_DESCRIPTION_WIDTH: usize = 20;
+
+/// Return the number of columns that the text fills, ignoring escape sequences such as colors.
+pub fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // control sequences such as ESC [ 1 m end with a character from @ to ~
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+        } else {
+            width += 1;
+        }
+    }
+
+    width
+}
+
+/// Return the text broken into lines no wider than the width, at spaces where possible.
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines: Vec<String> = vec![];
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+
+        if !line.is_empty() && line.chars().count() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+
+        // words wider than the width break wherever they must
+        while line.is_empty() && word.len() > width {
+            lines.push(word.drain(..width).collect());
+        }
+
+        if !line.is_empty() {
+            line.push(' ');
+        }
+
+        line.extend(word);
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+/// Return the text cut to the width, ending with an ellipsis if it was wider.
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Return the number of columns of cells of the given width that fit in the width of the terminal,
+/// from 1 to MAX_COLUMNS, but no more than the number of cells.
+pub fn columns(cell_width: usize, width: usize, count: usize) -> usize {
+    let columns = (width + GAP.len()) / (cell_width + GAP.len()).max(1);
+    columns.clamp(1, MAX_COLUMNS).min(count.max(1))
+}
+
+/// Write the command codes and descriptions as a table that fits the width of the terminal:
+/// several columns, filled down and then across, if the widest entry fits more than once,
+/// and otherwise one column with descriptions wrapped beside the command codes.
+/// Command codes wider than a third of the terminal end with an ellipsis so that the columns stay aligned.
+pub fn write_table<W: Write>(out: &mut Output<W>, entries: &[(String, String)], width: usize) -> std::io::Result<()> {
+    let code_width = entries
+        .iter()
+        .map(|(code, _description)| code.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(width / 3);
+    let description_width = entries
+        .iter()
+        .map(|(_code, description)| description.chars().count())
+        .max()
+        .unwrap_or(0);
+    let cell_width = code_width + 1 + description_width;
+    let columns = columns(cell_width, width, entries.len());
+
+    if columns == 1 {
+        let wrap_width = width.saturating_sub(code_width + 1).max(MIN_DESCRIPTION_WIDTH);
+
+        for (code, description) in entries.iter() {
+            for (i, line) in wrap(description, wrap_width).iter().enumerate() {
+                if i == 0 {
+                    write!(out, "{0} ", out.code(&format!("{0:>1$}", truncate(code, code_width), code_width)))?;
+                } else {
+                    write!(out, "{0:1$} ", "", code_width)?;
+                }
+
+                writeln!(out, "{}", out.description(line))?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    let rows = entries.len().div_ceil(columns);
+
+    for row in 0..rows {
+        let cells: Vec<&(String, String)> = (0..columns).filter_map(|column| entries.get(column * rows + row)).collect();
+
+        for (i, (code, description)) in cells.iter().enumerate() {
+            write!(out, "{0} ", out.code(&format!("{0:>1$}", truncate(code, code_width), code_width)))?;
+            write!(out, "{}", out.description(description))?;
+
+            // pad every cell but the last, so that trailing spaces do not wrap in terminals
+            if i + 1 < cells.len() {
+                write!(out, "{0:1$}{2}", "", description_width - description.chars().count(), GAP)?;
+            }
+        }
+
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::{columns, truncate, visible_width, wrap, write_table};
+
+    #[test]
+    fn it_wraps_and_fits_columns() {
+        assert_eq!(wrap("Microsoft Windows Subsystem for Linux", 16), vec!["Microsoft", "Windows", "Subsystem for", "Linux"]);
+        assert_eq!(wrap("C:/Program Files", 4), vec!["C:/P", "rogr", "am", "File", "s"]);
+        assert_eq!(wrap("", 10), vec![""]);
+        assert_eq!(visible_width("\u{1b}[38;5;14m\u{1b}[1mWORD\u{1b}[0m"), 4);
+        assert_eq!(columns(40, 80, 10), 1);
+        assert_eq!(columns(40, 83, 10), 2);
+        assert_eq!(columns(10, 200, 2), 2);
+
+        let entries: Vec<(String, String)> = ["a", "bb", "c", "d", "e"]
+            .iter()
+            .map(|code| (code.to_string(), format!("{} desc", code)))
+            .collect();
+        let mut out = crate::output::Output::new(vec![], crate::output::Theme::default(), false);
+        write_table(&mut out, &entries, 30).unwrap();
+        assert_eq!(
+            String::from_utf8(out.into_inner()).unwrap(),
+            " a a desc     d d desc\nbb bb desc    e e desc\n c c desc\n"
+        );
+
+        // command codes wider than a third of the terminal keep the columns aligned
+        assert_eq!(truncate("VSCODE|CODE|VSC", 6), "VSCOD…");
+        let mut entries = entries;
+        entries[0].0 = "averyverylongcodethatistoolong".to_string();
+        let mut out = crate::output::Output::new(vec![], crate::output::Theme::default(), false);
+        write_table(&mut out, &entries, 60).unwrap();
+        let text = String::from_utf8(out.into_inner()).unwrap();
+        let column = |line: &str, cell: &str| line.split(cell).next().unwrap().chars().count();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].starts_with("averyverylongcodeth… a desc"));
+        assert_eq!(column(lines[0], "d desc"), column(lines[1], "e desc"));
+    }
+}
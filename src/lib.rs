@@ -1,37 +1,114 @@
 //! This library contains the WinkConfig struct that represents
 //! command line options passed to the wink command.
 
+pub mod completions; // /src/completions.rs defines the contents of completions::
+pub mod config; // /src/config.rs defines the contents of config::
+pub mod docs; // /src/docs.rs defines the contents of docs::
+pub mod doctor; // /src/doctor.rs defines the contents of doctor::
 pub mod helperror; // /src/helperror.rs defines helperror::HelpError
+pub mod layout; // /src/layout.rs fits help tables to the width of the terminal
+pub mod output; // /src/output.rs defines output::Output and the color theme
+pub mod pager; // /src/pager.rs pages help that is longer than the terminal
+pub mod picker; // /src/picker.rs defines the interactive picker for wink without a command code
+pub mod suggest; // /src/suggest.rs defines functions for suggesting corrections
 pub mod winkconfig; // /src/winkconfig.rs defines winkconfig::WinkConfig
 pub mod wsl; // /wsl.rs defines the contents of wsl::
 
-use crate::wsl::inv::invocablecategory::InvocableCategory; // /src/wsl/inv/invocablecategory.rs
 use crate::wsl::inv::invocablecategorylist::InvocableCategoryList; // /src/wsl/inv/invocablecategorylist.rs
+use crate::output::Output; // /src/output.rs
 use crate::wsl::inv::invoker::Invoker; // /src/wsl/inv/invoker.rs
+use std::io::Write;
+
+/// The command code that selects the wink search command, which lists the invocables that match search terms.
+pub const SEARCH_COMMAND_CODE: &str = "search";
+
+/// The command codes of the commands that wink runs itself before looking up invocables,
+/// which therefore cannot invoke an invocable with the same command code or alias.
+pub const RESERVED_COMMAND_CODES: [&str; 7] = [
+    crate::config::configcommand::CONFIG_COMMAND_CODE,
+    crate::completions::completionscommand::COMPLETIONS_COMMAND_CODE,
+    crate::completions::completecommand::COMPLETE_COMMAND_CODE,
+    crate::docs::docscommand::DOCS_COMMAND_CODE,
+    SEARCH_COMMAND_CODE,
+    crate::doctor::doctorcommand::DOCTOR_COMMAND_CODE,
+    "help",
+];
+
+/// Return true if the given command code or alias is reserved for a command that wink runs itself, ignoring case.
+pub fn is_reserved(code: &str) -> bool {
+    RESERVED_COMMAND_CODES.iter().any(|c| c.eq_ignore_ascii_case(code))
+}
 
 pub fn run(config: crate::winkconfig::WinkConfig, category_list: InvocableCategoryList) -> i32 {
     // -> Result<u8, Box<dyn std::error::Error>> {
+    // wink config maintains configuration files rather than invoking a command
+    if config.command_code == crate::config::configcommand::CONFIG_COMMAND_CODE {
+        return crate::config::configcommand::run(&config);
+    }
+
+    // wink completions writes a shell completion script rather than invoking a command
+    if config.command_code == crate::completions::completionscommand::COMPLETIONS_COMMAND_CODE {
+        return crate::completions::completionscommand::run(&config, &category_list);
+    }
+
+    // the scripts that wink completions writes call wink __complete to complete arguments
+    if config.command_code == crate::completions::completecommand::COMPLETE_COMMAND_CODE {
+        return crate::completions::completecommand::run(&config, &category_list);
+    }
+
+    // wink docs writes documentation for the command codes rather than invoking a command
+    if config.command_code == crate::docs::docscommand::DOCS_COMMAND_CODE {
+        return crate::docs::docscommand::run(&config, &category_list);
+    }
+
+    // wink search lists matching invocables rather than invoking a command
+    if config.command_code == SEARCH_COMMAND_CODE {
+        return Output::pager(config.color).run(|out| search(out, &config, &category_list));
+    }
+
+    // wink doctor checks invocables and configuration files rather than invoking a command
+    if config.command_code == crate::doctor::doctorcommand::DOCTOR_COMMAND_CODE {
+        return crate::doctor::doctorcommand::run(&config, &category_list);
+    }
+
+    // -s exports the JSON Schema for configuration files rather than invoking a command
+    if config.schema {
+        return export(&config, &crate::config::schema::json_schema());
+    }
+
+    // without a command code in a terminal, pick the invocable interactively rather than rendering help
+    if config.command_code.is_empty() && !(config.export || config.dry_run) {
+        return pick(config, category_list);
+    }
+
     // categories contain lists of invocables that map command codes to commands
-    if let Some(invocable) = category_list.get_invocable(&config.command_code) {
-        if config.export {
-            if config.pretty_print {
-                println!("{}", serde_json::to_string_pretty(&invocable).unwrap());
-            } else {
-                println!("{}", serde_json::to_string(&invocable).unwrap());
+    // command codes match aliases, ignore case, and can be abbreviated to any unambiguous prefix
+    if let Some(invocable) = category_list.find_invocable(&config.command_code) {
+        return invoke(config, invocable);
+    } else if let Some(name) = crate::wsl::inv::invocablecategorylist::excluded_category(&config.command_code) {
+        // categories compiled out with cargo features contain command codes that wink recognizes but cannot invoke
+        eprintln!(
+            "Command code {0} is in category {1}, which this build of {2} excludes",
+            config.command_code, name, config.cmd_name
+        );
+        return 1;
+    } else if !config.command_code.is_empty() && category_list.candidates(&config.command_code).len() > 1 {
+        return Output::stdout(config.color).run(|out| ambiguous(out, &config, &category_list));
+    } else if !config.command_code.is_empty() && !category_list.suggestions(&config.command_code).is_empty() {
+        // -a runs the best suggestion for an unrecognized command code unless another suggestion is as good
+        if config.auto_run {
+            if let Some(invocable) = category_list.best_suggestion(&config.command_code) {
+                eprintln!(
+                    "Command code {0} not recognized; running {1}",
+                    config.command_code, invocable.command_code
+                );
+                return invoke(config, invocable);
             }
         }
 
-        let invoker = Invoker {};
-        invoker.invoke(invocable, config.dry_run, config.verbose, config.cmd_args);
-        return 0;
+        return Output::stdout(config.color).run(|out| suggest(out, &config, &category_list));
     } else if config.export && config.command_code.is_empty() {
-        if config.pretty_print {
-            println!("{}", serde_json::to_string_pretty(&category_list).unwrap());
-        } else {
-            println!("{}", serde_json::to_string(&category_list).unwrap());
-        }
-
-        return 0;
+        return export(&config, &category_list);
     } else if (config.command_code.is_empty() || !config.export) && config.dry_run {
         return 0;
     }
@@ -39,204 +116,530 @@ pub fn run(config: crate::winkconfig::WinkConfig, category_list: InvocableCatego
     help(
         &format!("Command not recognized: {0}", config.command_code),
         config,
-        category_list.categories,
+        category_list,
         false,
     )
 }
 
+/// Export the invocable if -e, then invoke it, and return the exit code for the process.
+fn invoke(config: crate::winkconfig::WinkConfig, invocable: &crate::wsl::inv::invocable::Invocable) -> i32 {
+    if config.export && export(&config, invocable) != 0 {
+        return 1;
+    }
+
+    let invoker = Invoker {};
+    invoker.invoke(invocable, config.dry_run, config.verbose, config.cmd_args);
+    0
+}
+
+/// Invoke the invocable that the user picks interactively with the arguments that the user types,
+/// or render help if the terminal does not support the picker, and return the exit code for the process.
+fn pick(mut config: crate::winkconfig::WinkConfig, category_list: InvocableCategoryList) -> i32 {
+    let picked = match crate::picker::pick(&category_list) {
+        Ok(Some((invocable, args))) => Some((invocable.clone(), args)),
+        Ok(None) => return 1,
+        Err(e) => {
+            eprintln!("Unable to show the picker: {0}", e);
+            None
+        }
+    };
+
+    match picked {
+        Some((invocable, args)) => {
+            config.cmd_args = args;
+            invoke(config, &invocable)
+        }
+        None => help("No command code found on command line", config, category_list, false),
+    }
+}
+
+/// Write the invocables that an unrecognized command code might have meant, with their categories and descriptions,
+/// instead of the entire help, and return the exit code for the process.
+fn suggest<W: Write>(
+    out: &mut Output<W>,
+    config: &crate::winkconfig::WinkConfig,
+    category_list: &InvocableCategoryList,
+) -> std::io::Result<i32> {
+    writeln!(out, "Command not recognized: {0}; did you mean one of these?", config.command_code)?;
+
+    for (category, invocable, _score) in category_list.suggestions(&config.command_code) {
+        let codes: Vec<&str> = invocable.codes().map(|c| c.as_str()).collect();
+        writeln!(out, "{0:>31} : {1} : {2}", codes.join("|"), category.name, invocable.description)?;
+    }
+
+    writeln!(out, "
+Run {0} -a {1} to run the best match, or {0} -h for all command codes.", config.cmd_name, config.command_code)?;
+    Ok(1)
+}
+
+/// Write the invocables that match the search terms after search, best first,
+/// in the style of the tables in help, and return the exit code for the process.
+fn search<W: Write>(
+    out: &mut Output<W>,
+    config: &crate::winkconfig::WinkConfig,
+    category_list: &InvocableCategoryList,
+) -> std::io::Result<i32> {
+    if config.cmd_args.is_empty() {
+        eprintln!("{0} search <term>...", config.cmd_name);
+        return Ok(1);
+    }
+
+    let matches = category_list.search(&config.cmd_args);
+
+    if matches.is_empty() {
+        writeln!(out, "No command codes match: {0}", config.cmd_args.join(" "))?;
+        return Ok(1);
+    }
+
+    writeln!(
+        out,
+        "\nCommand codes matching {0} : {1}\n{2}",
+        config.cmd_args.join(" "),
+        matches.len(),
+        out.rule()
+    )?;
+
+    if let Some(width) = out.width() {
+        let entries: Vec<(String, String)> = matches
+            .iter()
+            .map(|(category, invocable, _score)| {
+                let (codes, description) = table_entry(invocable);
+                (codes, format!("{0} : {1}", description, category.name))
+            })
+            .collect();
+        crate::layout::write_table(out, &entries, width)?;
+        return Ok(0);
+    }
+
+    for (category, invocable, _score) in matches {
+        write_invocable(out, invocable)?;
+        writeln!(out, " : {0}", category.name)?;
+    }
+
+    Ok(0)
+}
+
+/// Write the table of the invocables in the category, sorted by command code,
+/// and return the number of invocables.
+fn write_category<W: Write>(
+    out: &mut Output<W>,
+    category: &crate::wsl::inv::invocablecategory::InvocableCategory,
+) -> std::io::Result<usize> {
+    writeln!(out, "\n{0}\n{1}", category.name, out.rule())?;
+
+    let mut invocables: Vec<&crate::wsl::inv::invocable::Invocable> = category.invocables.iter().collect();
+    invocables.sort();
+
+    // terminals get tables that fit their width; pipes and files get the fixed layout
+    if let Some(width) = out.width() {
+        let entries: Vec<(String, String)> = invocables.iter().map(|invocable| table_entry(invocable)).collect();
+        crate::layout::write_table(out, &entries, width)?;
+        return Ok(invocables.len());
+    }
+
+    for invocable in invocables.iter() {
+        write_invocable(out, invocable)?;
+        writeln!(out)?;
+    }
+
+    Ok(invocables.len())
+}
+
+/// Write help about the category with the name after help, ignoring case, or otherwise about the invocable
+/// with the command code, or otherwise about the invocables that match the terms,
+/// and return the exit code for the process.
+fn help_topic<W: Write>(
+    out: &mut Output<W>,
+    config: &crate::winkconfig::WinkConfig,
+    category_list: &InvocableCategoryList,
+) -> std::io::Result<i32> {
+    let topic = config.cmd_args.join(" ");
+
+    if let Some(category) = category_list.categories.iter().find(|c| c.name.eq_ignore_ascii_case(&topic)) {
+        let count = write_category(out, category)?;
+        writeln!(out, "\n{0:>12} : {1} command codes in {2}\n", config.cmd_name, count, category.name)?;
+        return Ok(0);
+    }
+
+    if config.cmd_args.len() == 1 {
+        if let Some(invocable) = category_list.find_invocable(&topic) {
+            let category = category_list
+                .categories
+                .iter()
+                .find(|c| c.invocables.iter().any(|i| std::ptr::eq(i, invocable)));
+            write_details(out, config, category.map_or("", |c| c.name.as_str()), invocable)?;
+            return Ok(0);
+        }
+    }
+
+    search(out, config, category_list)
+}
+
+/// Write the details of the invocable: its description, category, aliases, command and the command line
+/// that wink would invoke, launch mode, arguments, tags, keywords, configuration file, and example command lines.
+fn write_details<W: Write>(
+    out: &mut Output<W>,
+    config: &crate::winkconfig::WinkConfig,
+    category: &str,
+    invocable: &crate::wsl::inv::invocable::Invocable,
+) -> std::io::Result<()> {
+    let tokens = crate::wsl::inv::tokenvalues::TokenValues::get();
+    let invoker = Invoker {};
+    let command_line = invoker.invoke_with(invocable, &tokens, true, false, vec![]);
+    let code = &invocable.command_code;
+
+    writeln!(out, "\n{}", out.rule())?;
+    write!(out, "{0} : ", out.code(&format!("{:>13}", code.to_uppercase())))?;
+    write!(out, "{}", out.description(&invocable.description))?;
+    writeln!(out, "\n{}", out.rule())?;
+    writeln!(out, "{0:>13} : {1}", "category", category)?;
+
+    if !invocable.aliases.is_empty() {
+        writeln!(out, "{0:>13} : {1}", "aliases", invocable.aliases.join(", "))?;
+    }
+
+    if !invocable.command.is_empty() {
+        writeln!(out, "{0:>13} : {1}", "command", invocable.command)?;
+        writeln!(out, "{0:>13} : {1}", "resolves to", Invoker::resolve_command(invocable, &tokens))?;
+    }
+
+    writeln!(out, "{0:>13} : {1}", "mode", invocable.mode)?;
+
+    if !invocable.arguments.is_empty() {
+        writeln!(out, "{0:>13} : {1}", "arguments", invocable.arguments.join(" "))?;
+    }
+
+    if !invocable.argument_kind.is_none() {
+        writeln!(out, "{0:>13} : {1}", "argument kind", invocable.argument_kind)?;
+    }
+
+    if !invocable.tags.is_empty() {
+        writeln!(out, "{0:>13} : {1}", "tags", invocable.tags.join(", "))?;
+    }
+
+    if !invocable.keywords.is_empty() {
+        writeln!(out, "{0:>13} : {1}", "keywords", invocable.keywords)?;
+    }
+
+    if invocable.source.is_built_in() {
+        writeln!(out, "{0:>13} : {1}", "source", invocable.source.layer)?;
+    } else {
+        writeln!(out, "{0:>13} : {1} configuration : {2}", "source", invocable.source.layer, invocable.source.path)?;
+    }
+
+    writeln!(out, "{0:>13} : {1}", "command line", command_line.trim_end())?;
+    writeln!(out, "{}", out.rule())?;
+    writeln!(out, "{0:>13} : {1} {2}", "examples", config.cmd_name, code)?;
+
+    if let Some(example) = invocable.argument_kind.example() {
+        writeln!(out, "{0:>13}   {1} {2} {3}", "", config.cmd_name, code, example)?;
+    }
+
+    writeln!(out, "{0:>13}   {1} -v -d {2} (print the command line without invoking it)", "", config.cmd_name, code)?;
+    writeln!(out, "{0:>13}   {1} -ep {2} (export the invocable)\n", "", config.cmd_name, code)
+}
+
+/// Return the command code and aliases of the invocable, as in VSCODE|CODE|VSC, and its description,
+/// or otherwise its command, with the configuration layer that defined it, if any, for layout::write_table().
+fn table_entry(invocable: &crate::wsl::inv::invocable::Invocable) -> (String, String) {
+    let codes: Vec<&str> = invocable.codes().map(|c| c.as_str()).collect();
+    let mut description = if invocable.description.is_empty() {
+        invocable.command.clone()
+    } else {
+        invocable.description.clone()
+    };
+
+    if !invocable.source.is_built_in() {
+        description.push_str(&format!(" [{0}]", invocable.source.layer));
+    }
+
+    (codes.join("|").to_uppercase(), description)
+}
+
+/// Write the command code and aliases of the invocable in the color of codes, then its description,
+/// or otherwise its command, in the color of descriptions, then the configuration layer that defined it, if any,
+/// without ending the line.
+fn write_invocable<W: Write>(out: &mut Output<W>, invocable: &crate::wsl::inv::invocable::Invocable) -> std::io::Result<()> {
+    // aliases follow the command code, as in VSCODE|CODE|VSC
+    let codes: Vec<&str> = invocable.codes().map(|c| c.as_str()).collect();
+    let description = if invocable.description.is_empty() {
+        &invocable.command
+    } else {
+        &invocable.description
+    };
+
+    write!(out, "{0} ", out.code(&format!("{:>31}", codes.join("|").to_uppercase())))?;
+    write!(out, "{}", out.description(description))?;
+
+    if !invocable.source.is_built_in() {
+        write!(out, " [{0}]", invocable.source.layer)?;
+    }
+
+    Ok(())
+}
+
+/// Write the command codes that start with an ambiguous prefix, with their categories and descriptions,
+/// and return the exit code for the process.
+fn ambiguous<W: Write>(
+    out: &mut Output<W>,
+    config: &crate::winkconfig::WinkConfig,
+    category_list: &InvocableCategoryList,
+) -> std::io::Result<i32> {
+    writeln!(out, "Command code {0} is ambiguous; did you mean one of these?", config.command_code)?;
+
+    for (category, invocable) in category_list.candidates(&config.command_code) {
+        let codes: Vec<&str> = invocable.codes().map(|c| c.as_str()).collect();
+        writeln!(out, "{0:>31} : {1} : {2}", codes.join("|"), category.name, invocable.description)?;
+    }
+
+    Ok(1)
+}
+
+/// Write the given value to stdout in the format selected by --format, pretty-printed if -p,
+/// and return the exit code for the process.
+fn export<T: serde::Serialize>(config: &crate::winkconfig::WinkConfig, value: &T) -> i32 {
+    match config.format.to_string(value, config.pretty_print) {
+        Ok(text) => Output::stdout(config.color).run(|out| {
+            writeln!(out, "{}", text)?;
+            Ok(0)
+        }),
+        Err(e) => {
+            eprintln!("Unable to export {0}: {1}", config.format, e);
+            1
+        }
+    }
+}
+
 /// The help() function renders usage information about the wink command to stdout.
 /// The msg argument is a message indicating why the command rendered usage information.
 /// The args argument is the command line including the invoked command (wink) and command line arguments.
-/// The categories argument contains lists of invocables used to render usage information.
+/// The category_list argument contains lists of invocables used to render usage information
+/// and the configuration files that defined them.
 pub fn help(
     msg: &str,
     config: crate::winkconfig::WinkConfig,
-    mut categories: Vec<InvocableCategory>,
+    category_list: InvocableCategoryList,
     help_requested: bool,
 ) -> i32 {
+    Output::pager(config.color).run(|out| {
+        // wink help <category|code|term> renders help about one category or invocable, or the invocables that match
+        if help_requested && config.command_code.eq_ignore_ascii_case("help") && !config.cmd_args.is_empty() {
+            help_topic(out, &config, &category_list)
+        } else {
+            write_help(out, msg, &config, &category_list, help_requested)
+        }
+    })
+}
+
+/// Write the usage information and the tables of command codes for help(),
+/// coloring the message as an error unless the user requested help.
+fn write_help<W: Write>(
+    out: &mut Output<W>,
+    msg: &str,
+    config: &crate::winkconfig::WinkConfig,
+    category_list: &InvocableCategoryList,
+    help_requested: bool,
+) -> std::io::Result<i32> {
+    let mut categories: Vec<&crate::wsl::inv::invocablecategory::InvocableCategory> =
+        category_list.categories.iter().collect();
     // cmd = basename(wink.exe)
     //    let cmd = regex::Regex::new(r".*[\\/](?P<name>[^\\/]+$)").unwrap().replace_all(args[0].as_str(), "$name");
     //TODO: render invoked command line from config.
-    print!(
+    write!(
+        out,
         "
------------------------------------------------------------------------------
+{rule}
 {0:>12} : access  Windows and WSL features : {1}
------------------------------------------------------------------------------
+{rule}
 {0:>12} : invoked as : {2}
------------------------------------------------------------------------------
+{rule}
 {0:>12} ",
         config.cmd_name,
         msg,
-        config.all_args.join(" ")
-    );
-    cyan("EXP");
+        config.all_args.join(" "),
+        rule = out.rule()
+    )?;
+    write!(out, "{}", out.code("EXP"))?;
 
-    print!(
+    write!(
+        out,
         "                explorer.exe
------------------------------------------------------------------------------
+{rule}
 {0:>12} ",
-        config.cmd_name
-    );
-    cyan("EXP");
-    print!(
+        config.cmd_name,
+        rule = out.rule()
+    )?;
+    write!(out, "{}", out.code("EXP"))?;
+    write!(
+        out,
         " <file.ext>     Set/open default application for extension
 {0:>12} ",
         config.cmd_name
-    );
+    )?;
 
-    cyan("EXP");
-    print!(
+    write!(out, "{}", out.code("EXP"))?;
+    write!(
+        out,
         " <shell:sendto> Invoke command code (replace <shell:sendto>)
------------------------------------------------------------------------------
+{rule}
 
------------------------------------------------------------------------------
+{rule}
 {0:>12} ",
-        config.cmd_name
-    );
-    cyan("CMD");
-    print!(
+        config.cmd_name,
+        rule = out.rule()
+    )?;
+    write!(out, "{}", out.code("CMD"))?;
+    write!(
+        out,
         "                cmd.exe /c
------------------------------------------------------------------------------
+{rule}
 {0:>12} ",
-        config.cmd_name
-    );
-    cyan("CMD");
-    print!(
+        config.cmd_name,
+        rule = out.rule()
+    )?;
+    write!(out, "{}", out.code("CMD"))?;
+    write!(
+        out,
         " <cmd> [args]   Invoke Windows console command line
 {0:>12} ",
         config.cmd_name
-    );
-    cyan("CMD");
-    print!(
+    )?;
+    write!(out, "{}", out.code("CMD"))?;
+    write!(
+        out,
         " echo %PATH%    Display Windows environment variable
------------------------------------------------------------------------------
+{rule}
 
------------------------------------------------------------------------------
+{rule}
 {0:>12} ",
-        config.cmd_name
-    );
-    cyan("BASH");
-    print!(
+        config.cmd_name,
+        rule = out.rule()
+    )?;
+    write!(out, "{}", out.code("BASH"))?;
+    write!(
+        out,
         "               bash.exe -c
------------------------------------------------------------------------------
+{rule}
 {0:>12} ",
-        config.cmd_name
-    );
-    cyan("BASH");
-    print!(
+        config.cmd_name,
+        rule = out.rule()
+    )?;
+    write!(out, "{}", out.code("BASH"))?;
+    write!(
+        out,
         " /path [args]  Invoke shell command line
 {0:>12} ",
         config.cmd_name
-    );
-    cyan("BASH");
-    print!(
+    )?;
+    write!(out, "{}", out.code("BASH"))?;
+    write!(
+        out,
         " echo '$USER'  Display WSL environment variable
------------------------------------------------------------------------------
+{rule}
 
------------------------------------------------------------------------------
+{rule}
 {0:>12} ",
-        config.cmd_name
-    );
-    cyan("CODE");
-    println!(
+        config.cmd_name,
+        rule = out.rule()
+    )?;
+    write!(out, "{}", out.code("CODE"))?;
+    writeln!(
+        out,
         " [args]        See command code tables below
------------------------------------------------------------------------------",
-    );
+{rule}",
+        rule = out.rule()
+    )?;
 
     let mut count = 0;
     categories.sort();
-//    let mut terminal = term::stdout().unwrap();
-
-    for mut category in categories {
-        println!(
-            "\n{0}\n-----------------------------------------------------------------------------",
-            category.name
-        );
-        category.invocables.sort();
 
-        for invocable in category.invocables {
-            let mut desc = invocable.description;
+    for category in categories.iter() {
+        count += write_category(out, category)?;
+    }
 
-            if desc.is_empty() {
-                desc = invocable.command;
-            }
+    writeln!(
+        out,
+        "\n{0:>12} : {1} known command codes\n",
+        config.cmd_name, count
+    )?;
 
-            cyan(&format!("{:>31}", invocable.command_code.to_uppercase()));
-            print!(" ");
-            blue(&desc);
-            println!();
-            count += 1;
+    for source in category_list.sources.iter() {
+        if source.included_by.is_empty() {
+            writeln!(out, "{0:>12} : {1} configuration : {2}", config.cmd_name, source.layer, source.path)?;
+        } else {
+            writeln!(
+                out,
+                "{0:>12} : {1} configuration : {2} (included by {3})",
+                config.cmd_name, source.layer, source.path, source.included_by
+            )?;
         }
     }
 
-    println!(
-        "\n{0:>12} : {1} known command codes\n",
-        config.cmd_name, count
-    );
-    print!(
+    for (name, feature, _codes) in crate::wsl::inv::invocablecategorylist::EXCLUDED_CATEGORIES.iter() {
+        writeln!(out, "{0:>12} : excluded from this build : {1} (cargo feature {2})", config.cmd_name, name, feature)?;
+    }
+
+    if !category_list.sources.is_empty() || !crate::wsl::inv::invocablecategorylist::EXCLUDED_CATEGORIES.is_empty() {
+        writeln!(out)?;
+    }
+    write!(
+        out,
         "{0:>12} : access Windows features : ",
         config.cmd_name
-    );
+    )?;
 
     if help_requested {
-        cyan(msg);
-    } else {
-        red(msg);
-    }    
-        
-    print!("\n\n{0:>12} [opts] <", config.cmd_name);
-    cyan("CODE");
-    println!("> [arguments]");
-    println!("            -d dry (do not execute)");
-    println!("            -e export (configuraiton JSON)");
-    println!(
-        "            -h help (display this {0} command usage information)",
-        config.cmd_name
-    );
-    println!("            -p pretty-print (for use with -e)");
-    println!("            -v verbose (print command line)\n");
-    print!("{0} ", config.cmd_name);
-    cyan("HELP");
-    println!(" :                  display command usage information");
-    print!("{0} ", config.cmd_name);
-    cyan("HELP");
-
-    if cfg!(target_os = "windows") {
-        println!(" | find /i \"text\" :: identify command code matching text");
+        write!(out, "{}", out.code(msg))?;
     } else {
-        println!(" | grep -i \"text\" # identify command code matching text");
+        write!(out, "{}", out.error(msg))?;
     }
 
-    1
-}
-
-/// Writes the given message to STDOUT in a cyan other than the default.
-fn cyan(msg: &str) {
-    let mut terminal = term::stdout().unwrap();
-    terminal.fg(term::color::BRIGHT_CYAN).unwrap();
-    terminal.attr(term::Attr::Bold).unwrap();
-    print!("{0}", msg);
-    terminal.reset().unwrap();
-}
+    write!(out, "\n\n{0:>12} [opts] <", config.cmd_name)?;
+    write!(out, "{}", out.code("CODE"))?;
+    writeln!(out, "> [arguments]")?;
+    for (option, description) in crate::winkconfig::OPTIONS.iter() {
+        writeln!(out, "            {0} {1}", option, description)?;
+    }
 
-fn red(msg: &str) {
-    let mut terminal = term::stdout().unwrap();
-    terminal.fg(term::color::RED).unwrap();
-    terminal.attr(term::Attr::Bold).unwrap();
-    print!("{0}", msg);
-    terminal.reset().unwrap();
-}
+    writeln!(out)?;
+    writeln!(
+        out,
+        "{0} config migrate [path] : upgrade configuration file format",
+        config.cmd_name
+    )?;
+    writeln!(
+        out,
+        "{0} config add|edit|remove|move|list : change user configuration (run {0} config for options)",
+        config.cmd_name
+    )?;
+    writeln!(
+        out,
+        "{0} doctor [--json] [code]... : check commands and configuration (-v to include unchecked commands)",
+        config.cmd_name
+    )?;
+    writeln!(
+        out,
+        "{0} completions bash|zsh|fish|powershell : write a shell completion script for command codes and options",
+        config.cmd_name
+    )?;
+    writeln!(
+        out,
+        "{0} docs markdown|html|csv|man : write documentation for command codes, including configured categories\n",
+        config.cmd_name
+    )?;
+    write!(out, "{0} ", config.cmd_name)?;
+    write!(out, "{}", out.code("HELP"))?;
+    writeln!(out, " :                  display command usage information")?;
+    write!(out, "{0} ", config.cmd_name)?;
+    write!(out, "{}", out.code("HELP"))?;
+    writeln!(out, " <category|code|term>... display one category, one invocable, or search results")?;
+    write!(out, "{0} ", config.cmd_name)?;
+    write!(out, "{}", out.code("SEARCH"))?;
+    writeln!(out, " <term>...        identify command codes matching codes, descriptions, tags, keywords, or commands")?;
 
-fn blue(msg: &str) {
-    let mut terminal = term::stdout().unwrap();
-    terminal.fg(term::color::BLUE).unwrap();
-    terminal.attr(term::Attr::Bold).unwrap();
-    print!("{0}", msg);
-    terminal.reset().unwrap();
+    Ok(1)
 }
 
-
-
 #[cfg(test)]
 mod tests {
     #[test]
@@ -245,4 +648,26 @@ mod tests {
     fn it_has_a_path() {
         panic!("it_has_a_path intentional panic to render path to parent of this tests module.")
     }
+
+    #[test]
+    #[cfg(feature = "cat-office")]
+    fn it_writes_help_without_color_unless_enabled() {
+        let args: Vec<String> = vec!["wink".to_string(), "help".to_string()];
+        let config = match crate::winkconfig::WinkConfig::new(args) {
+            Ok(config) => config,
+            Err((config, _e)) => config,
+        };
+        let category_list = crate::wsl::inv::invocablecategorylist::InvocableCategoryList::built_in();
+        let theme = crate::output::Theme::default();
+
+        let mut plain = crate::output::Output::new(vec![], theme, false);
+        crate::write_help(&mut plain, "Help requested by help", &config, &category_list, true).unwrap();
+        let plain = String::from_utf8(plain.into_inner()).unwrap();
+        assert!(plain.contains("                           WORD Microsoft Word"));
+        assert!(!plain.contains('\u{1b}'));
+
+        let mut colored = crate::output::Output::new(vec![], theme, true);
+        crate::write_help(&mut colored, "Help requested by help", &config, &category_list, true).unwrap();
+        assert!(String::from_utf8(colored.into_inner()).unwrap().contains('\u{1b}'));
+    }
 }
@@ -0,0 +1,365 @@
+//! The wink.output module writes help and other reports to stdout through a single buffered writer,
+//! coloring command codes, descriptions, and errors with the theme only when color is enabled:
+//! --color=always, or --color=auto (the default) when stdout is a terminal and NO_COLOR does not disable color.
+//! The WINK_COLORS environment variable selects the theme, such as code=green:description=none:error=bright-red.
+
+use crossterm::style::{Color, Stylize};
+use std::io::{IsTerminal, Write};
+
+/// The names of the values of the --color command line option.
+pub const COLOR_CHOICE_NAMES: [&str; 3] = ["auto", "always", "never"];
+
+/// The environment variable that selects the colors of the theme.
+pub const THEME_VARIABLE: &str = "WINK_COLORS";
+
+/// The names of the colors for the theme, where none leaves text plain.
+pub const COLOR_NAMES: [&str; 17] = [
+    "none",
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright-black",
+    "bright-red",
+    "bright-green",
+    "bright-yellow",
+    "bright-blue",
+    "bright-magenta",
+    "bright-cyan",
+    "bright-white",
+];
+
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    /// Color output to terminals unless NO_COLOR is set or CLICOLOR is 0, or to anything if CLICOLOR_FORCE is set.
+    #[default]
+    Auto,
+
+    /// Color output even when stdout is not a terminal.
+    Always,
+
+    /// Never color output.
+    Never,
+}
+
+impl ColorChoice {
+    /// Return the choice with the given name, ignoring case, such as never, if any.
+    pub fn from_name(name: &str) -> Option<ColorChoice> {
+        match name.to_lowercase().as_str() {
+            "auto" => Some(ColorChoice::Auto),
+            "always" => Some(ColorChoice::Always),
+            "never" => Some(ColorChoice::Never),
+            _ => None,
+        }
+    }
+
+    /// Return true if output to stdout should be colored, following https://no-color.org and the CLICOLOR conventions.
+    pub fn enabled(&self) -> bool {
+        let var = |name: &str| std::env::var(name).unwrap_or_default();
+
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto if !var("NO_COLOR").is_empty() => false,
+            ColorChoice::Auto if !matches!(var("CLICOLOR_FORCE").as_str(), "" | "0") => true,
+            ColorChoice::Auto if var("CLICOLOR") == "0" => false,
+            ColorChoice::Auto => std::io::stdout().is_terminal() && supports_ansi(),
+        }
+    }
+}
+
+/// Return true if the console interprets ANSI escape sequences, enabling them in Windows consoles that support them.
+#[cfg(windows)]
+fn supports_ansi() -> bool {
+    crossterm::ansi_support::supports_ansi()
+}
+
+/// Return true if the console interprets ANSI escape sequences, which terminals outside Windows do.
+#[cfg(not(windows))]
+fn supports_ansi() -> bool {
+    true
+}
+
+/// The colors of the parts of the output, or None for plain text. Colored text is bold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Command codes, such as WORD in help tables.
+    pub code: Option<Color>,
+
+    /// Descriptions of invocables.
+    pub description: Option<Color>,
+
+    /// The reason that wink rendered help instead of invoking a command.
+    pub error: Option<Color>,
+}
+
+impl Default for Theme {
+    fn default() -> Theme {
+        Theme {
+            code: Some(Color::Cyan),
+            description: Some(Color::DarkBlue),
+            error: Some(Color::DarkRed),
+        }
+    }
+}
+
+impl Theme {
+    /// Return the default theme with the colors in the WINK_COLORS environment variable, if set,
+    /// writing any entries that it cannot parse to stderr.
+    pub fn get() -> Theme {
+        let spec = std::env::var(THEME_VARIABLE).unwrap_or_default();
+        let (theme, invalid) = Theme::parse(&spec);
+
+        for entry in invalid {
+            eprintln!(
+                "Ignoring {0} entry: {1} (expected code, description, or error = {2})",
+                THEME_VARIABLE,
+                entry,
+                COLOR_NAMES.join("|")
+            );
+        }
+
+        theme
+    }
+
+    /// Return the default theme with the colors in the given specification, such as code=green:error=none,
+    /// and the entries that do not name a part and a color.
+    pub fn parse(spec: &str) -> (Theme, Vec<String>) {
+        let mut theme = Theme::default();
+        let mut invalid: Vec<String> = vec![];
+
+        for entry in spec.split(':').map(|e| e.trim()).filter(|e| !e.is_empty()) {
+            let (part, name) = entry.split_once('=').unwrap_or((entry, ""));
+            let color = match color(name) {
+                Some(color) => color,
+                None => {
+                    invalid.push(entry.to_string());
+                    continue;
+                }
+            };
+
+            match part.trim().to_lowercase().as_str() {
+                "code" => theme.code = color,
+                "description" => theme.description = color,
+                "error" => theme.error = color,
+                _ => invalid.push(entry.to_string()),
+            }
+        }
+
+        (theme, invalid)
+    }
+}
+
+/// Return the color with the given name, ignoring case, such as bright-cyan, or Some(None) for none.
+fn color(name: &str) -> Option<Option<Color>> {
+    let color = match name.trim().to_lowercase().replace('_', "-").as_str() {
+        "none" => return Some(None),
+        "black" => Color::Black,
+        "red" => Color::DarkRed,
+        "green" => Color::DarkGreen,
+        "yellow" => Color::DarkYellow,
+        "blue" => Color::DarkBlue,
+        "magenta" => Color::DarkMagenta,
+        "cyan" => Color::DarkCyan,
+        "white" => Color::Grey,
+        "bright-black" => Color::DarkGrey,
+        "bright-red" => Color::Red,
+        "bright-green" => Color::Green,
+        "bright-yellow" => Color::Yellow,
+        "bright-blue" => Color::Blue,
+        "bright-magenta" => Color::Magenta,
+        "bright-cyan" => Color::Cyan,
+        "bright-white" => Color::White,
+        _ => return None,
+    };
+
+    Some(Some(color))
+}
+
+/// The width of the rules between sections of help when stdout is not a terminal, or the widest rule in a terminal.
+pub const RULE_WIDTH: usize = 77;
+
+/// Return the columns and rows of the terminal if stdout is a terminal that reports its size.
+pub fn terminal_size() -> Option<(usize, usize)> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+
+    match crossterm::terminal::size() {
+        Ok((columns, rows)) if columns > 0 && rows > 0 => Some((columns as usize, rows as usize)),
+        _ => None,
+    }
+}
+
+/// Where an Output for stdout writes: through a buffer to stdout, or to memory for the pager when done.
+pub enum Stdout {
+    Buffered(std::io::BufWriter<std::io::Stdout>),
+    Paged(Vec<u8>),
+}
+
+impl Write for Stdout {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Stdout::Buffered(writer) => writer.write(buf),
+            Stdout::Paged(buffer) => buffer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Stdout::Buffered(writer) => writer.flush(),
+            Stdout::Paged(_buffer) => Ok(()),
+        }
+    }
+}
+
+/// A writer that colors parts of the text with the theme if color is enabled,
+/// and that knows the width of the terminal, if any, for laying out tables.
+pub struct Output<W: Write> {
+    writer: W,
+    theme: Theme,
+    color: bool,
+    width: Option<usize>,
+}
+
+impl Output<Stdout> {
+    /// Return an Output that buffers text for stdout, with the theme from WINK_COLORS
+    /// if the choice enables color.
+    pub fn stdout(choice: ColorChoice) -> Output<Stdout> {
+        let color = choice.enabled();
+        let theme = if color { Theme::get() } else { Theme::default() };
+        let width = terminal_size().map(|(columns, _rows)| columns);
+        Output::new(Stdout::Buffered(std::io::BufWriter::new(std::io::stdout())), theme, color).with_width(width)
+    }
+
+    /// Return an Output like stdout(), but that pages the text when done
+    /// if stdout is a terminal and the text is longer than the terminal.
+    pub fn pager(choice: ColorChoice) -> Output<Stdout> {
+        let mut output = Output::stdout(choice);
+
+        if output.width.is_some() && crate::pager::enabled() {
+            output.writer = Stdout::Paged(vec![]);
+        }
+
+        output
+    }
+
+    /// Call the function to write to this Output, flush or page it, and return the exit code from the function.
+    /// If the reader of a pipe closes it, such as wink help | head, stop writing quietly;
+    /// write other errors to stderr and return 1.
+    pub fn run<F>(mut self, f: F) -> i32
+    where
+        F: FnOnce(&mut Self) -> std::io::Result<i32>,
+    {
+        let result = f(&mut self).and_then(|code| match &mut self.writer {
+            Stdout::Buffered(writer) => writer.flush().map(|_| code),
+            Stdout::Paged(buffer) => crate::pager::page(&String::from_utf8_lossy(buffer)).map(|_| code),
+        });
+
+        match result {
+            Ok(code) => code,
+            Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => 0,
+            Err(e) => {
+                eprintln!("Unable to write output: {0}", e);
+                1
+            }
+        }
+    }
+}
+
+impl<W: Write> Output<W> {
+    /// Return an Output that writes to the given writer, coloring text with the theme if color is true,
+    /// in the fixed layout for output that is not a terminal.
+    pub fn new(writer: W, theme: Theme, color: bool) -> Output<W> {
+        Output {
+            writer,
+            theme,
+            color,
+            width: None,
+        }
+    }
+
+    /// Return this Output with the width of the terminal in columns, or None for the fixed layout.
+    pub fn with_width(mut self, width: Option<usize>) -> Output<W> {
+        self.width = width;
+        self
+    }
+
+    /// Return the width of the terminal in columns, or None if help should use the fixed layout.
+    pub fn width(&self) -> Option<usize> {
+        self.width
+    }
+
+    /// Return a rule that separates sections of help, no wider than the terminal.
+    pub fn rule(&self) -> String {
+        "-".repeat(self.width.map_or(RULE_WIDTH, |width| width.min(RULE_WIDTH)))
+    }
+
+    /// Return the text in the color of command codes, if enabled.
+    pub fn code(&self, text: &str) -> String {
+        self.paint(self.theme.code, text)
+    }
+
+    /// Return the text in the color of descriptions, if enabled.
+    pub fn description(&self, text: &str) -> String {
+        self.paint(self.theme.description, text)
+    }
+
+    /// Return the text in the color of errors, if enabled.
+    pub fn error(&self, text: &str) -> String {
+        self.paint(self.theme.error, text)
+    }
+
+    fn paint(&self, color: Option<Color>, text: &str) -> String {
+        match color {
+            Some(color) if self.color => text.with(color).bold().to_string(),
+            _ => text.to_string(),
+        }
+    }
+
+    /// Return the writer, such as to inspect what the Output wrote.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> Write for Output<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::{ColorChoice, Output, Theme};
+    use crossterm::style::Color;
+
+    #[test]
+    fn it_colors_only_when_enabled() {
+        let (theme, invalid) = Theme::parse("code=green: description=NONE:error=bright_red:code=teal:size=blue");
+        assert_eq!(theme.code, Some(Color::DarkGreen));
+        assert_eq!(theme.description, None);
+        assert_eq!(theme.error, Some(Color::Red));
+        assert_eq!(invalid, vec!["code=teal", "size=blue"]);
+
+        let plain = Output::new(vec![], Theme::default(), false);
+        assert_eq!(plain.code("WORD"), "WORD");
+
+        let colored = Output::new(vec![], theme, true);
+        assert!(colored.code("WORD").starts_with('\u{1b}'));
+        assert_eq!(colored.description("Microsoft Word"), "Microsoft Word");
+
+        assert_eq!(ColorChoice::from_name("NEVER"), Some(ColorChoice::Never));
+        assert!(!ColorChoice::Never.enabled());
+        assert!(ColorChoice::Always.enabled());
+    }
+}
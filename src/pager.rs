@@ -0,0 +1,161 @@
+//! The wink.pager module pages text that is longer than the terminal, such as help,
+//! through the command in WINK_PAGER or PAGER, or otherwise through a built-in pager.
+//! Setting either variable to an empty value disables paging.
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::io::{IsTerminal, Write};
+
+/// The environment variables that select the pager, in order of precedence.
+pub const PAGER_VARIABLES: [&str; 2] = ["WINK_PAGER", "PAGER"];
+
+/// The prompt of the built-in pager.
+const PROMPT: &str = "-- More -- (Space for the next page, Enter for the next line, q to quit)";
+
+/// Return the pager command from the first of WINK_PAGER and PAGER that is set, which can be empty, if any.
+fn command() -> Option<String> {
+    PAGER_VARIABLES.iter().find_map(|name| std::env::var(name).ok())
+}
+
+/// Return true unless WINK_PAGER or PAGER disables paging.
+pub fn enabled() -> bool {
+    match command() {
+        Some(command) => !command.trim().is_empty(),
+        None => true,
+    }
+}
+
+/// Return the number of terminal rows that the text fills at the given width,
+/// ignoring the escape sequences that color it.
+pub fn rows(text: &str, columns: usize) -> usize {
+    text.lines()
+        .map(|line| crate::layout::visible_width(line).max(1).div_ceil(columns.max(1)))
+        .sum()
+}
+
+/// Write the text to stdout, through the pager if it fills more than the terminal.
+pub fn page(text: &str) -> std::io::Result<()> {
+    let (columns, screen) = crate::output::terminal_size().unwrap_or((80, 24));
+
+    if rows(text, columns) < screen {
+        return write_stdout(text);
+    }
+
+    match command() {
+        Some(command) if command.trim().is_empty() => write_stdout(text),
+        Some(command) => match external(&command, text) {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                eprintln!("Unable to run pager {0}: {1}", command, e);
+                built_in(text, columns, screen)
+            }
+        },
+        None => built_in(text, columns, screen),
+    }
+}
+
+/// Write the text to stdout without paging.
+fn write_stdout(text: &str) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(text.as_bytes())?;
+    stdout.flush()
+}
+
+/// Write the text to the standard input of the pager command, run by the shell so that it can include options,
+/// such as less -R, and wait for the user to quit it. Like git, set LESS to keep colors if it is not set.
+fn external(command: &str, text: &str) -> std::io::Result<()> {
+    let mut shell = if cfg!(target_os = "windows") {
+        let mut shell = std::process::Command::new("cmd.exe");
+        shell.arg("/C").arg(command);
+        shell
+    } else {
+        let mut shell = std::process::Command::new("sh");
+        shell.arg("-c").arg(command);
+        shell
+    };
+
+    if std::env::var_os("LESS").is_none() {
+        shell.env("LESS", "FRX");
+    }
+
+    let mut child = shell.stdin(std::process::Stdio::piped()).spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // the user can quit the pager before reading everything
+        match stdin.write_all(text.as_bytes()) {
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => return Err(e),
+            _ => {}
+        }
+    }
+
+    // shells exit with 127, and cmd.exe with 9009, if they cannot find the command
+    match child.wait()?.code() {
+        Some(127) | Some(9009) => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "command not found")),
+        _ => Ok(()),
+    }
+}
+
+/// Write the text to stdout a screen at a time, waiting for a key after each screen,
+/// or all of it if stdin is not a terminal that can read keys.
+fn built_in(text: &str, columns: usize, screen: usize) -> std::io::Result<()> {
+    if !std::io::stdin().is_terminal() {
+        return write_stdout(text);
+    }
+
+    let page = screen.saturating_sub(1).max(1);
+    let mut stdout = std::io::stdout().lock();
+    let mut shown = 0;
+    let mut limit = page;
+
+    for line in text.lines() {
+        if shown >= limit {
+            stdout.write_all(PROMPT.chars().take(columns.saturating_sub(1)).collect::<String>().as_bytes())?;
+            stdout.flush()?;
+            let key = read_key();
+            stdout.write_all(b"\r\x1b[K")?;
+
+            match key? {
+                KeyCode::Char(' ') | KeyCode::PageDown => limit = shown + page,
+                KeyCode::Enter | KeyCode::Down | KeyCode::Char('j') => limit = shown + 1,
+                _ => return stdout.flush(),
+            }
+        }
+
+        stdout.write_all(line.as_bytes())?;
+        stdout.write_all(b"\n")?;
+        shown += rows(line, columns);
+    }
+
+    stdout.flush()
+}
+
+/// Read keys in raw mode until the user presses one, treating Ctrl+C as Esc.
+fn read_key() -> std::io::Result<KeyCode> {
+    crossterm::terminal::enable_raw_mode()?;
+
+    let key = loop {
+        // Windows reports key releases as well as presses
+        match crossterm::event::read() {
+            Ok(Event::Key(KeyEvent { code: KeyCode::Char('c'), modifiers, kind, .. }))
+                if modifiers.contains(KeyModifiers::CONTROL) && kind != KeyEventKind::Release =>
+            {
+                break Ok(KeyCode::Esc)
+            }
+            Ok(Event::Key(key)) if key.kind != KeyEventKind::Release => break Ok(key.code),
+            Ok(_) => continue,
+            Err(e) => break Err(e),
+        }
+    };
+
+    crossterm::terminal::disable_raw_mode()?;
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_counts_rows_without_colors() {
+        let colored = "\u{1b}[38;5;14m\u{1b}[1mWORD\u{1b}[0m Microsoft Word";
+        assert_eq!(crate::pager::rows(colored, 80), 1);
+        assert_eq!(crate::pager::rows("abcdefghij\n\nabc", 4), 5);
+    }
+}
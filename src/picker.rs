@@ -0,0 +1,320 @@
+//! The wink.picker module contains an interactive terminal picker that fuzzy-filters invocables
+//! by command code, alias, description, and category as the user types,
+//! for wink run without a command code.
+
+use crate::suggest::fuzzy_score; // /src/suggest.rs
+use crate::wsl::inv::invocable::Invocable; // /src/wsl/inv/invocable.rs
+use crate::wsl::inv::invocablecategory::InvocableCategory; // /src/wsl/inv/invocablecategory.rs
+use crate::wsl::inv::invocablecategorylist::InvocableCategoryList; // /src/wsl/inv/invocablecategorylist.rs
+use crate::wsl::inv::invoker::Invoker; // /src/wsl/inv/invoker.rs
+use crate::wsl::inv::tokenvalues::TokenValues; // /src/wsl/inv/tokenvalues.rs
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::{cursor, execute, queue, terminal};
+use std::io::{IsTerminal, Write};
+
+/// Lines of the picker other than the list of invocables: the filter, the arguments, the count, and the preview.
+const CHROME_LINES: u16 = 5;
+
+/// Return true if both stdin and stdout are terminals, so that wink can show the picker instead of help.
+pub fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// Return the categories and invocables that match the filter, best first: command codes and aliases,
+/// then descriptions, then category names, breaking ties by category name and command code.
+pub fn filter<'a>(category_list: &'a InvocableCategoryList, query: &str) -> Vec<(&'a InvocableCategory, &'a Invocable)> {
+    let mut matches: Vec<(usize, &InvocableCategory, &Invocable)> = vec![];
+
+    for category in category_list.categories.iter() {
+        for invocable in category.invocables.iter() {
+            let codes: Vec<&str> = invocable.codes().map(|c| c.as_str()).collect();
+            let score = [
+                fuzzy_score(query, &codes.join("|")),
+                fuzzy_score(query, &invocable.description).map(|s| s + 1),
+                fuzzy_score(query, &category.name).map(|s| s + 2),
+            ]
+            .iter()
+            .flatten()
+            .min()
+            .copied();
+
+            if let Some(score) = score {
+                matches.push((score, category, invocable));
+            }
+        }
+    }
+
+    matches.sort_by(|(s1, c1, i1), (s2, c2, i2)| (s1, &c1.name, &i1.command_code).cmp(&(s2, &c2.name, &i2.command_code)));
+    matches.into_iter().map(|(_score, category, invocable)| (category, invocable)).collect()
+}
+
+/// The state of the picker: the filter and arguments that the user has typed, which of them has the focus,
+/// the selected line and the first line shown in the list of matches, the number of invocables,
+/// the token values for previewing command lines, which wink gets once because that starts cmd.exe,
+/// and the last preview with its command code and arguments, which wink keeps because converting paths starts wslpath.
+#[derive(Default)]
+struct Picker {
+    tokens: TokenValues,
+    preview: Option<(String, String, String)>,
+    total: usize,
+    query: String,
+    arguments: String,
+    editing_arguments: bool,
+    selected: usize,
+    scroll: usize,
+}
+
+/// Show the picker until the user selects an invocable or cancels, and return the invocable with the arguments
+/// that the user typed, or None if cancelled. The picker uses the alternate screen and restores the terminal when done.
+pub fn pick(category_list: &InvocableCategoryList) -> std::io::Result<Option<(&Invocable, Vec<String>)>> {
+    let tokens = TokenValues::get();
+    let mut stdout = std::io::stdout();
+    let _terminal = RawTerminal::enter(&mut stdout)?;
+    run(category_list, tokens, &mut stdout)
+}
+
+/// Leaves the alternate screen and raw mode when dropped, whether the picker returns or fails.
+struct RawTerminal;
+
+impl RawTerminal {
+    /// Enable raw mode and enter the alternate screen, restoring the terminal if entering the alternate screen fails.
+    fn enter<W: Write>(out: &mut W) -> std::io::Result<RawTerminal> {
+        terminal::enable_raw_mode()?;
+        let raw_terminal = RawTerminal;
+        execute!(out, terminal::EnterAlternateScreen)?;
+        Ok(raw_terminal)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _discard = execute!(std::io::stdout(), terminal::LeaveAlternateScreen);
+        let _discard = terminal::disable_raw_mode();
+    }
+}
+
+/// Render the picker and handle keys until the user selects an invocable with Enter or cancels with Esc or Ctrl+C.
+fn run<'a, W: Write>(
+    category_list: &'a InvocableCategoryList,
+    tokens: TokenValues,
+    out: &mut W,
+) -> std::io::Result<Option<(&'a Invocable, Vec<String>)>> {
+    let mut picker = Picker {
+        tokens,
+        total: filter(category_list, "").len(),
+        ..Default::default()
+    };
+
+    loop {
+        let matches = filter(category_list, &picker.query);
+        picker.selected = picker.selected.min(matches.len().saturating_sub(1));
+        render(&mut picker, &matches, out)?;
+
+        // Windows reports key releases as well as presses
+        let key = match crossterm::event::read()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => key,
+            _ => continue,
+        };
+
+        match key {
+            KeyEvent { code: KeyCode::Esc, .. } => return Ok(None),
+            KeyEvent { code: KeyCode::Char('c'), modifiers, .. } if modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(None)
+            }
+            KeyEvent { code: KeyCode::Enter, .. } => {
+                if let Some((_category, invocable)) = matches.get(picker.selected) {
+                    return Ok(Some((invocable, split_arguments(&picker.arguments))));
+                }
+            }
+            KeyEvent { code: KeyCode::Tab, .. } | KeyEvent { code: KeyCode::BackTab, .. } => {
+                picker.editing_arguments = !picker.editing_arguments
+            }
+            KeyEvent { code: KeyCode::Up, .. } => picker.selected = picker.selected.saturating_sub(1),
+            KeyEvent { code: KeyCode::Down, .. } => picker.selected += 1,
+            KeyEvent { code: KeyCode::PageUp, .. } => picker.selected = picker.selected.saturating_sub(list_height()),
+            KeyEvent { code: KeyCode::PageDown, .. } => picker.selected += list_height(),
+            KeyEvent { code: KeyCode::Backspace, .. } => {
+                picker.field().pop();
+            }
+            KeyEvent { code: KeyCode::Char(c), modifiers, .. } if !modifiers.contains(KeyModifiers::CONTROL) => {
+                picker.field().push(c);
+
+                if !picker.editing_arguments {
+                    picker.selected = 0;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Picker {
+    /// Return the command line that wink would invoke for the invocable with the arguments that the user typed,
+    /// as -v -d writes it, reusing the last preview if neither has changed.
+    fn preview(&mut self, invocable: &Invocable) -> String {
+        match &self.preview {
+            Some((code, arguments, command_line)) if *code == invocable.command_code && *arguments == self.arguments => {
+                command_line.clone()
+            }
+            _ => {
+                let (_command, command_line) = Invoker::command(invocable, &self.tokens, &split_arguments(&self.arguments));
+                self.preview = Some((invocable.command_code.clone(), self.arguments.clone(), command_line.clone()));
+                command_line
+            }
+        }
+    }
+
+    /// Return the text that has the focus: the filter or the arguments.
+    fn field(&mut self) -> &mut String {
+        if self.editing_arguments {
+            &mut self.arguments
+        } else {
+            &mut self.query
+        }
+    }
+}
+
+/// Return the columns and rows of the terminal, or 80 by 24 if the terminal does not report its size.
+fn size() -> (u16, u16) {
+    match terminal::size() {
+        Ok((columns, rows)) if columns > 0 && rows > 0 => (columns, rows),
+        _ => (80, 24),
+    }
+}
+
+/// Return the number of lines available for the list of matches.
+fn list_height() -> usize {
+    let (_columns, rows) = size();
+    rows.saturating_sub(CHROME_LINES).max(1) as usize
+}
+
+/// Split the arguments that the user typed at whitespace, keeping whitespace within double quotes.
+fn split_arguments(arguments: &str) -> Vec<String> {
+    let mut result: Vec<String> = vec![];
+    let mut current: Option<String> = None;
+    let mut quoted = false;
+
+    for c in arguments.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                current.get_or_insert_with(String::new);
+            }
+            c if c.is_whitespace() && !quoted => result.extend(current.take()),
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    result.extend(current);
+    result
+}
+
+/// Render the filter, the arguments, the matches around the selected line,
+/// and the command line that wink would invoke for the selected invocable, as with -v -d.
+fn render<W: Write>(picker: &mut Picker, matches: &[(&InvocableCategory, &Invocable)], out: &mut W) -> std::io::Result<()> {
+    let (columns, _rows) = size();
+    let width = columns as usize;
+    let height = list_height();
+
+    if picker.selected < picker.scroll {
+        picker.scroll = picker.selected;
+    } else if picker.selected >= picker.scroll + height {
+        picker.scroll = picker.selected + 1 - height;
+    }
+
+    queue!(out, Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+    queue!(out, Print(truncate(&format!("filter    : {0}", picker.query), width)), Print("\r\n"))?;
+    queue!(out, Print(truncate(&format!("arguments : {0}", picker.arguments), width)), Print("\r\n"))?;
+    queue!(
+        out,
+        Print(truncate(
+            &format!("{0} of {1} : Tab to switch, Enter to run, Esc to cancel", matches.len(), picker.total),
+            width
+        )),
+        Print("\r\n")
+    )?;
+
+    for (line, (category, invocable)) in matches.iter().enumerate().skip(picker.scroll).take(height) {
+        let codes: Vec<&str> = invocable.codes().map(|c| c.as_str()).collect();
+        let text = truncate(&format!("{0:>16} : {1} : {2}", codes.join("|"), category.name, invocable.description), width);
+
+        if line == picker.selected {
+            queue!(out, SetAttribute(Attribute::Reverse), Print(text), SetAttribute(Attribute::Reset), Print("\r\n"))?;
+        } else {
+            queue!(out, Print(text), Print("\r\n"))?;
+        }
+    }
+
+    if let Some((_category, invocable)) = matches.get(picker.selected) {
+        let command_line = picker.preview(invocable);
+        let (_columns, rows) = size();
+        queue!(out, cursor::MoveTo(0, rows.saturating_sub(1)), Print(truncate(&command_line, width)))?;
+    }
+
+    let (column, row) = if picker.editing_arguments {
+        (12 + picker.arguments.chars().count(), 1)
+    } else {
+        (12 + picker.query.chars().count(), 0)
+    };
+
+    queue!(out, cursor::MoveTo(column.min(width.saturating_sub(1)) as u16, row))?;
+    out.flush()
+}
+
+/// Return the text cut to fit the width of the terminal.
+fn truncate(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(all(feature = "cat-office", feature = "cat-sysinternals"))]
+    fn it_filters_invocables() {
+        let category_list = crate::wsl::inv::invocablecategorylist::InvocableCategoryList::built_in();
+        let all = crate::picker::filter(&category_list, "");
+        assert_eq!(all.len(), category_list.categories.iter().map(|c| c.invocables.len()).sum::<usize>());
+
+        let matches = crate::picker::filter(&category_list, "word");
+        assert_eq!(matches[0].1.command_code, "word");
+
+        // descriptions and categories match after command codes
+        let matches = crate::picker::filter(&category_list, "process monitor");
+        assert_eq!(matches[0].1.command_code, "procmon");
+        assert!(crate::picker::filter(&category_list, "zzzzqq").is_empty());
+    }
+
+    #[test]
+    fn it_previews_command_lines_as_invoked() {
+        use crate::wsl::inv::invocable::Invocable;
+        use crate::wsl::inv::invoker::Invoker;
+        let tokens = crate::wsl::inv::tokenvalues::TokenValues {
+            pf64: "C:/Program Files".to_string(),
+            ..Default::default()
+        };
+        let mut picker = crate::picker::Picker {
+            tokens: tokens.clone(),
+            arguments: r#"/q "C:/My Documents/a.docx""#.to_string(),
+            ..Default::default()
+        };
+        let arguments = crate::picker::split_arguments(&picker.arguments);
+
+        for invocable in [
+            Invocable::bin("word", "$pf64/WINWORD.EXE", "Word"),
+            Invocable::cmd("dir", "dir", "Directory"),
+            Invocable::sh("ls", "ls", "List files"),
+        ] {
+            let expected = Invoker {}.invoke_with(&invocable, &tokens, true, false, arguments.clone());
+            assert_eq!(picker.preview(&invocable), expected);
+        }
+    }
+
+    #[test]
+    fn it_splits_arguments() {
+        assert_eq!(crate::picker::split_arguments(r#" /q  "C:/My Documents/a.docx" "#), vec!["/q", "C:/My Documents/a.docx"]);
+        assert!(crate::picker::split_arguments("  ").is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+//! The wink.suggest module contains functions for finding the closest match to a misspelled word.
+
+/// Return the number of single-character insertions, deletions, substitutions, and transpositions
+/// of adjacent characters required to change one string into the other (optimal string alignment distance),
+/// which counts typing two characters in the wrong order as a single typo.
+pub fn typo_distance(first: &str, second: &str) -> usize {
+    let first: Vec<char> = first.chars().collect();
+    let second: Vec<char> = second.chars().collect();
+    let mut rows: Vec<Vec<usize>> = vec![(0..=second.len()).collect()];
+
+    for i in 1..=first.len() {
+        let mut current = vec![i];
+
+        for j in 1..=second.len() {
+            let cost = if first[i - 1] == second[j - 1] { 0 } else { 1 };
+            let mut distance = (rows[i - 1][j - 1] + cost).min(rows[i - 1][j] + 1).min(current[j - 1] + 1);
+
+            if i > 1 && j > 1 && first[i - 1] == second[j - 2] && first[i - 2] == second[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+
+            current.push(distance);
+        }
+
+        rows.push(current);
+    }
+
+    rows[first.len()][second.len()]
+}
+
+/// Return the typo distance between the given word and candidate, ignoring case,
+/// if the candidate is close enough that the word is likely to be a misspelling of it.
+pub fn close_distance(word: &str, candidate: &str) -> Option<usize> {
+    let word = word.to_lowercase();
+    let limit = std::cmp::max(2, word.chars().count() / 3);
+    let distance = typo_distance(&word, &candidate.to_lowercase());
+
+    if distance <= limit {
+        Some(distance)
+    } else {
+        None
+    }
+}
+
+/// Return the candidate closest to the given word, if any candidate is close enough
+/// that the word is likely to be a misspelling of it.
+pub fn closest<'a>(word: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .filter_map(|c| close_distance(word, c).map(|distance| (distance, *c)))
+        .min_by_key(|(distance, _c)| *distance)
+        .map(|(_distance, c)| c)
+}
+
+/// Return a score for how well the characters of the query appear in the text in order, ignoring case,
+/// which is lower for matches with fewer characters between them and matches that start at a word,
+/// or None if the characters of the query do not all appear in the text in order.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let mut best: Option<usize> = None;
+
+    for start in (0..text.len()).filter(|i| text[*i] == query[0]) {
+        let mut matched = 1;
+        let mut end = start;
+
+        for (i, c) in text.iter().enumerate().skip(start + 1) {
+            if matched == query.len() {
+                break;
+            }
+
+            if *c == query[matched] {
+                matched += 1;
+                end = i;
+            }
+        }
+
+        if matched < query.len() {
+            break;
+        }
+
+        let at_word = start == 0 || !text[start - 1].is_alphanumeric();
+        let score = (end + 1 - start - query.len()) * 2 + if at_word { 0 } else { 1 };
+        best = Some(best.map_or(score, |b| b.min(score)));
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_counts_transpositions_as_typos() {
+        assert_eq!(super::typo_distance("", "bash"), 4);
+        assert_eq!(super::typo_distance("use_bash", "use_bash"), 0);
+        assert_eq!(super::typo_distance("use_cdm", "use_cmd"), 1);
+        assert_eq!(super::typo_distance("wrod", "word"), 1);
+        assert_eq!(super::typo_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn it_scores_fuzzy_matches() {
+        assert_eq!(super::fuzzy_score("", "Microsoft Word"), Some(0));
+        assert_eq!(super::fuzzy_score("word", "Microsoft Word"), Some(0));
+        assert_eq!(super::fuzzy_score("msw", "Microsoft Word"), Some(16));
+        assert!(super::fuzzy_score("wrd", "Microsoft Word") < super::fuzzy_score("crd", "Microsoft Word"));
+        assert_eq!(super::fuzzy_score("xl", "Microsoft Word"), None);
+    }
+
+    #[test]
+    fn it_finds_the_closest_candidate() {
+        let candidates = ["command_code", "description", "command", "arguments"];
+        assert_eq!(super::closest("argumnets", &candidates), Some("arguments"));
+        assert_eq!(super::closest("Descripton", &candidates), Some("description"));
+        assert_eq!(super::closest("xyzzy", &candidates), None);
+    }
+}
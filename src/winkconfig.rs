@@ -1,5 +1,19 @@
 //mod helperror;
 
+/// The command line options that WinkConfig::new parses, with their descriptions for help and shell completion,
+/// where --format=json|toml|yaml lists the values of the option.
+pub const OPTIONS: [(&str, &str); 9] = [
+    ("-a", "auto-run (best match for an unrecognized command code)"),
+    ("--color=auto|always|never", "color (auto colors terminals unless NO_COLOR is set)"),
+    ("-d", "dry (do not execute)"),
+    ("-e", "export (configuration JSON)"),
+    ("--format=json|toml|yaml", "format (for use with -e or -s)"),
+    ("-h", "help (display command usage information)"),
+    ("-p", "pretty-print (for use with -e or -s)"),
+    ("-s", "schema (JSON Schema for configuration files)"),
+    ("-v", "verbose (print command line)"),
+];
+
 /// The WinkConfig struct represents command line options passed
 /// to the wink command.
 #[derive(serde::Serialize)]
@@ -13,6 +27,9 @@ pub struct WinkConfig {
     /// DryRun: true if the -d command line option is present. Do not run the command.
     pub dry_run: bool,
 
+    /// AutoRun: true if the -a command line option is present. Run the best suggestion for an unrecognized command code.
+    pub auto_run: bool,
+
     /// the command code entered by the user, such as EXP or CMD.
     pub command_code: String,
 
@@ -22,6 +39,15 @@ pub struct WinkConfig {
     /// PrettyPrint: true if the -p command line option is present. Pretty-print JSON exports.
     pub pretty_print: bool,
 
+    /// Schema: true if the -s command line option is present. Export the JSON Schema for configuration files.
+    pub schema: bool,
+
+    /// Format: the --format=json|toml|yaml command line option. The format for -e and -s exports.
+    pub format: crate::config::configformat::ConfigFormat,
+
+    /// Color: the --color=auto|always|never command line option. Whether help and other reports use color.
+    pub color: crate::output::ColorChoice,
+
     /// all of the arguments on the command line, including cmd_name
     pub all_args: Vec<String>,
 
@@ -45,11 +71,16 @@ impl std::fmt::Display for WinkConfig {
 impl WinkConfig {
     /// The get_from_cmd_line_args function return a WinkConfig
     /// created from parsing the command line.
+    #[allow(clippy::result_large_err)]
     pub fn new(args: Vec<String>) -> Result<WinkConfig, (WinkConfig, crate::helperror::HelpError)> {
         let mut dry_run: bool = false; // -d command line option
         let mut verbose: bool = false; // -v command line option
+        let mut auto_run: bool = false; // -a command line option
         let mut export: bool = false; // -e command line option
         let mut pretty_print: bool = false; // -p command line option
+        let mut schema: bool = false; // -s command line option
+        let mut format: Option<crate::config::configformat::ConfigFormat> = None; // --format command line option
+        let mut color: crate::output::ColorChoice = Default::default(); // --color command line option
         let mut first_arg_index = 1; // number of processed command line arguments (first is command name, such as wink)
         let mut help_msg = String::new();
         let mut bad_args: bool = false;
@@ -67,13 +98,50 @@ impl WinkConfig {
                 break;
             }
 
+            // long options such as --format=toml take a value rather than combining with other options
+            if let Some(option) = arg.strip_prefix("--") {
+                match option.split_once('=') {
+                    Some(("format", name)) => match crate::config::configformat::ConfigFormat::from_name(name) {
+                        Some(f) => format = Some(f),
+                        None => {
+                            bad_args = true;
+                            help_msg = format!(
+                                "Unrecognized format: {0} (expected {1})",
+                                name,
+                                crate::config::configformat::CONFIG_FORMAT_NAMES.join(", ")
+                            );
+                        }
+                    },
+                    Some(("color", name)) => match crate::output::ColorChoice::from_name(name) {
+                        Some(c) => color = c,
+                        None => {
+                            bad_args = true;
+                            help_msg = format!(
+                                "Unrecognized color choice: {0} (expected {1})",
+                                name,
+                                crate::output::COLOR_CHOICE_NAMES.join(", ")
+                            );
+                        }
+                    },
+                    _ => {
+                        bad_args = true;
+                        help_msg = format!("Unrecognized command line option: {0}", arg);
+                    }
+                }
+
+                first_arg_index += 1;
+                continue;
+            }
+
             for char in arg.chars() {
                 match char {
                     '/' | '-' => continue,
                     'v' => verbose = true,
                     'd' => dry_run = true,
+                    'a' => auto_run = true,
                     'p' => pretty_print = true,
                     'e' => export = true,
+                    's' => schema = true,
                     'h' | '?' => {
                         help_msg = format!("Help requested by {0}", arg);
                         break;
@@ -103,9 +171,13 @@ impl WinkConfig {
                 .to_string(),
             verbose,
             dry_run,
+            auto_run,
             command_code,
             export,
             pretty_print,
+            schema,
+            format: format.unwrap_or_default(),
+            color,
             cmd_args: (args[first_arg_index..]).to_vec(),
             all_args: args,
         };
@@ -113,9 +185,13 @@ impl WinkConfig {
         if help_msg.is_empty() {
             if !crate::wsl::is_windows_or_wsl() {
                 help_msg = "Runs only under Windows and Windows Subsystem for Linux (WSL). Define WSL_DISTRO_NAME environment variable to override.".to_string();
-            } else if result.pretty_print && !result.export {
-                help_msg = "-p invalid without -e".to_string();
-            } else if result.command_code.is_empty() && !(result.export || result.dry_run) {
+            } else if result.pretty_print && !(result.export || result.schema) {
+                help_msg = "-p invalid without -e or -s".to_string();
+            } else if format.is_some() && !(result.export || result.schema) {
+                help_msg = "--format invalid without -e or -s".to_string();
+            } else if result.command_code.is_empty()
+                && !(result.export || result.dry_run || result.schema || crate::picker::is_interactive())
+            {
                 help_msg = "No command code found on command line".to_string();
             }
         }
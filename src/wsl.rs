@@ -8,7 +8,6 @@ pub mod inv; // interpret /inv.rs as wink::wsl::inv
 /// The arg argument is the path to the file.
 /// The unix argument indicates whether to convert that path to Unix or Windows.
 /// let param: &String = &wsl_path_or_self("C:\\", true /*unix*/ );
-
 pub fn wsl_path_or_self(arg: &str, unix: bool) -> String {
     let exists = std::path::Path::new(arg).exists();
 
@@ -66,13 +65,12 @@ fn get_user_home() -> Result<String, Box<dyn std::error::Error>> {
 }
 
 fn get_user_home_default() -> String {
-    match get_user_home() {
-        Ok(h) => h,
-        Err(_e) => String::new(),
-    }
+    get_user_home().unwrap_or_default()
 }
 
-fn get_config_file_path(name: &str) -> String {
+/// Return the path to the user configuration file with the given name,
+/// such as $HOME/.wink.json (WSL) or %USERPROFILE%\wink.json (Windows).
+pub fn get_config_file_path(name: &str) -> String {
     if is_windows() {
         format!("{0}\\{1}", get_user_home_default(), name)
     } else {
@@ -80,6 +78,56 @@ fn get_config_file_path(name: &str) -> String {
     }
 }
 
+/// Return the path to the configuration file with the given name shared by all users,
+/// such as /etc/wink/wink.json (WSL) or %ProgramData%\wink\wink.json (Windows).
+pub fn get_system_config_file_path(name: &str) -> String {
+    if is_windows() {
+        let data = std::env::var("ProgramData").unwrap_or_else(|_| "C:\\ProgramData".to_string());
+        format!("{0}\\wink\\{1}", data, name)
+    } else {
+        format!("/etc/wink/{0}", name)
+    }
+}
+
+/// Return the path to the directory of drop-in configuration files for the current user,
+/// such as $XDG_CONFIG_HOME/wink/conf.d (WSL, defaulting to $HOME/.config/wink/conf.d)
+/// or %APPDATA%\wink\conf.d (Windows).
+pub fn get_drop_in_config_dir_path() -> String {
+    if is_windows() {
+        let data = std::env::var("APPDATA").unwrap_or_else(|_| format!("{0}\\AppData\\Roaming", get_user_home_default()));
+        format!("{0}\\wink\\conf.d", data)
+    } else {
+        // the XDG Base Directory Specification ignores relative paths
+        match std::env::var("XDG_CONFIG_HOME") {
+            Ok(config) if config.starts_with('/') => format!("{0}/wink/conf.d", config),
+            _ => format!("{0}/.config/wink/conf.d", get_user_home_default()),
+        }
+    }
+}
+
+/// Return the paths to the .<name> files for the given names in the nearest directory,
+/// starting with the current directory and walking up through its parents, that contains any of them,
+/// such as .wink.json and .wink.toml in the root of a project.
+pub fn find_project_config_file_paths(names: &[&str]) -> Vec<String> {
+    let mut dir = match std::env::current_dir() {
+        Ok(dir) => dir,
+        Err(_) => return vec![],
+    };
+
+    loop {
+        let found: Vec<String> = names
+            .iter()
+            .map(|name| dir.join(format!(".{0}", name)))
+            .filter(|candidate| candidate.is_file())
+            .map(|candidate| candidate.to_string_lossy().to_string())
+            .collect();
+
+        if !found.is_empty() || !dir.pop() {
+            return found;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -113,9 +161,8 @@ mod tests {
     #[test]
     fn is_windows_or_wsl() {
         println!("wsl::tests::is_windows_or_wsl()");
-        assert_eq!(
+        assert!(
             super::is_windows_or_wsl(),
-            true,
             "Run the tests against the Linux binary under WSL."
         );
     }
@@ -123,9 +170,8 @@ mod tests {
     #[test]
     fn is_windows() {
         println!("wsl::tests::is_windows()");
-        assert_eq!(
-            super::is_windows(),
-            false,
+        assert!(
+            !super::is_windows(),
             "Run the tests against the Linux binary under WSL."
         );
     }
@@ -133,9 +179,8 @@ mod tests {
     #[test]
     fn is_wsl() {
         println!("wsl::tests::is_wsl()");
-        assert_eq!(
+        assert!(
             super::is_wsl(),
-            true,
             "Run the tests against the Linux binary under WSL."
         );
     }
@@ -1,4 +1,8 @@
+pub mod argumentkind;
 pub mod invocable;
 pub mod invocablecategory;
 pub mod invocablecategorylist;
 pub mod invoker;
+pub mod launchmode;
+pub mod tokens;
+pub mod tokenvalues;
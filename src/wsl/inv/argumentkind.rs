@@ -0,0 +1,78 @@
+//! An ArgumentKind identifies what the arguments that the user passes to an Invocable name,
+//! such as files or URLs, so that shell completion can offer matching values.
+//! Configuration files specify the kind by name, such as "argument_kind": "file",
+//! or list the values to offer, such as "argument_kind": { "choice": [ "on", "off" ] }.
+
+/// The names of the argument kinds in configuration files.
+pub const ARGUMENT_KIND_NAMES: [&str; 5] = ["file", "directory", "url", "distro", "choice"];
+
+#[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ArgumentKind {
+    /// The arguments are not known, so shells complete them as they would for any command.
+    #[default]
+    None,
+
+    /// Paths to files or directories, in the Linux or Windows form that the user starts typing.
+    File,
+
+    /// Paths to directories, in the Linux or Windows form that the user starts typing.
+    Directory,
+
+    /// URLs, such as https://github.com.
+    Url,
+
+    /// Names of Windows Subsystem for Linux distributions, such as Ubuntu.
+    Distro,
+
+    /// One of the listed values.
+    Choice(Vec<String>),
+}
+
+impl ArgumentKind {
+    /// Return true if the kind of the arguments is not known.
+    pub fn is_none(&self) -> bool {
+        *self == ArgumentKind::None
+    }
+
+    /// Return an example argument of this kind for help, such as <path> or the first choice, if the kind is known.
+    pub fn example(&self) -> Option<String> {
+        match self {
+            ArgumentKind::None => None,
+            ArgumentKind::File => Some("<path>".to_string()),
+            ArgumentKind::Directory => Some("<directory>".to_string()),
+            ArgumentKind::Url => Some("https://<host>".to_string()),
+            ArgumentKind::Distro => Some("<distro>".to_string()),
+            ArgumentKind::Choice(choices) => choices.first().cloned(),
+        }
+    }
+}
+
+impl std::fmt::Display for ArgumentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArgumentKind::None => write!(f, "none"),
+            ArgumentKind::File => write!(f, "{}", ARGUMENT_KIND_NAMES[0]),
+            ArgumentKind::Directory => write!(f, "{}", ARGUMENT_KIND_NAMES[1]),
+            ArgumentKind::Url => write!(f, "{}", ARGUMENT_KIND_NAMES[2]),
+            ArgumentKind::Distro => write!(f, "{}", ARGUMENT_KIND_NAMES[3]),
+            ArgumentKind::Choice(choices) => write!(f, "{0} of {1}", ARGUMENT_KIND_NAMES[4], choices.join(", ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wsl::inv::argumentkind::ArgumentKind;
+
+    #[test]
+    fn it_describes_argument_kinds() {
+        assert_eq!(ArgumentKind::Directory.to_string(), "directory");
+        assert_eq!(ArgumentKind::Directory.example(), Some("<directory>".to_string()));
+        assert_eq!(ArgumentKind::None.example(), None);
+
+        let choice = ArgumentKind::Choice(vec!["on".to_string(), "off".to_string()]);
+        assert_eq!(choice.to_string(), "choice of on, off");
+        assert_eq!(choice.example(), Some("on".to_string()));
+    }
+}
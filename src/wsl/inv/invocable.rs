@@ -1,35 +1,152 @@
 //! An Invocable contains metadata about a command that Windows, cmd.exe, explorer.exe, or bash.exe can invoke.
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+
+use crate::config::configsource::ConfigSource; // /src/config/configsource.rs
+use crate::wsl::inv::argumentkind::ArgumentKind; // /src/wsl/inv/argumentkind.rs
+use crate::wsl::inv::launchmode::LaunchMode; // /src/wsl/inv/launchmode.rs
+
+/// Every field except command_code is optional in configuration files, and exports omit default values.
+/// A category can also define an invocable as "code": "command" (see InvocableCategory).
+#[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema, Debug, Clone, Default)]
+#[serde(try_from = "InvocableDocument")]
 pub struct Invocable {
     /// The command code for the user to enter on the command line
     pub command_code: String, // command code for matching command line argument
 
+    /// Other command codes that the user can enter instead of command_code
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+
     /// A description of what the command does
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub description: String, // for help information
 
     /// The executable or Windows code such as shell:desktop or /path/to/script.
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub command: String, // path_to_program.exe, shell:desktop, etc.
 
-    /// If true, the invocable depends on cmd.exe.
-    pub use_cmd: bool, // cmd.exe [command]
+    /// How to invoke the command: directly, or through cmd.exe, explorer.exe, or bash.exe.
+    #[serde(skip_serializing_if = "LaunchMode::is_direct")]
+    pub mode: LaunchMode, // [explorer.exe | cmd.exe [/wait] /c [start [/b] | call] | bash.exe -c] <command>
+
+    /// Arguments to pass on the command line before those provided by the user.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<String>, // [explorer.exe | cmd.exe [/start | start /b] <command> [arguments]
+
+    /// What the arguments that the user passes name, such as files, for shell completion.
+    #[serde(skip_serializing_if = "ArgumentKind::is_none")]
+    pub argument_kind: ArgumentKind,
+
+    /// Labels that group invocables across categories, such as ms-settings or mmc, for search.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Other words that describe the invocable, for search.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub keywords: String,
+
+    /// The configuration layer and file that defined the invocable; omitted for built-in invocables.
+    #[serde(skip_serializing_if = "ConfigSource::is_built_in")]
+    pub source: ConfigSource,
+}
+
+/// The form of an Invocable in configuration files, which can specify the launch mode
+/// with the use_cmd, use_start, background, use_call, use_explorer, and use_bash flags
+/// of earlier versions of wink instead of mode.
+/// The doc comments on these fields become descriptions in the JSON Schema (see config::schema).
+#[derive(serde::Deserialize, schemars::JsonSchema, Default)]
+#[serde(deny_unknown_fields, default)]
+#[schemars(rename = "Invocable")]
+struct InvocableDocument {
+    /// The command code for the user to enter on the command line, such as word.
+    command_code: String,
+
+    /// Other command codes for the user to enter instead of command_code, such as code and vsc for vscode.
+    aliases: Vec<String>,
+
+    /// A description of what the command does, for help.
+    description: String,
+
+    /// The executable or Windows code such as shell:desktop or /path/to/script.
+    command: String,
+
+    /// How to invoke the command: directly (the default), or through cmd.exe, explorer.exe, or bash.exe.
+    mode: Option<LaunchMode>,
+
+    /// Deprecated: use "mode": "cmd".
+    #[schemars(extend("deprecated" = true))]
+    use_cmd: bool,
 
-    /// If true, the invocable depends on cmd.exe and uses start.
-    pub use_start: bool, // cmd.exe start [command] //TODO: replace start with call except start/b
+    /// Deprecated: use "mode": "cmd_start".
+    #[schemars(extend("deprecated" = true))]
+    use_start: bool,
 
-    /// If true, the invocable depnds on cmd.exe and uses start /b.
-    pub background: bool, // cmd.exe start /b [command]
+    /// Deprecated: use "mode": "cmd_start_background".
+    #[schemars(extend("deprecated" = true))]
+    background: bool,
 
-    /// If true, the invocable depends on cmd.exe and uses call.
-    pub use_call: bool, // cmd.exe call <command> [arguments]
+    /// Deprecated: use "mode": "cmd_call".
+    #[schemars(extend("deprecated" = true))]
+    use_call: bool,
 
-    /// If true, the invocable depends on explorer.exe.
-    pub use_explorer: bool, // [cmd.exe] explorer.exe [/start | start /b]] <command> [arguments]
+    /// Deprecated: use "mode": "explorer".
+    #[schemars(extend("deprecated" = true))]
+    use_explorer: bool,
 
-    /// If true, the invocable depends on bash.exe.
-    pub use_bash: bool, // bash.exe -c <command> [arguments]
+    /// Deprecated: use "mode": "bash".
+    #[schemars(extend("deprecated" = true))]
+    use_bash: bool,
 
     /// Arguments to pass on the command line before those provided by the user.
-    pub arguments: Vec<String>, // [explorer.exe | cmd.exe [/start | start /b] <command> [arguments]
+    arguments: Vec<String>,
+
+    /// What the arguments that the user passes name, for shell completion: file, directory, url, distro,
+    /// or values to choose from, such as { "choice": [ "on", "off" ] }.
+    argument_kind: ArgumentKind,
+
+    /// Labels that group invocables across categories for wink search, such as ms-settings or mmc.
+    tags: Vec<String>,
+
+    /// Other words that describe the invocable for wink search, such as spreadsheet for excel.
+    keywords: String,
+
+    /// Exports identify the configuration file that defined the invocable; wink ignores this field when reading.
+    #[schemars(extend("readOnly" = true))]
+    source: ConfigSource,
+}
+
+/// Validate the launch mode, converting any flags to a LaunchMode.
+impl std::convert::TryFrom<InvocableDocument> for Invocable {
+    type Error = String;
+
+    fn try_from(document: InvocableDocument) -> Result<Self, Self::Error> {
+        let flags = LaunchMode::from_flags(
+            document.use_cmd,
+            document.use_start,
+            document.background,
+            document.use_call,
+            document.use_explorer,
+            document.use_bash,
+        )?;
+
+        let mode = match document.mode {
+            Some(mode) if flags.is_direct() => mode,
+            Some(_mode) => return Err("specify either mode or launch flags, not both".to_string()),
+            None => flags,
+        };
+
+        Ok(Invocable {
+            command_code: document.command_code,
+            aliases: document.aliases,
+            description: document.description,
+            command: document.command,
+            mode,
+            arguments: document.arguments,
+            argument_kind: document.argument_kind,
+            tags: document.tags,
+            keywords: document.keywords,
+            source: document.source,
+        })
+    }
 }
 
 /// For sorting.
@@ -57,6 +174,22 @@ impl PartialEq for Invocable {
 }
 
 impl Invocable {
+    /// Return the command code and then the aliases of this invocable.
+    pub fn codes(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.command_code).chain(self.aliases.iter())
+    }
+
+    /// Return true if the given code is the command code or an alias of this invocable, ignoring case.
+    pub fn has_code(&self, code: &str) -> bool {
+        self.codes().any(|c| c.eq_ignore_ascii_case(code))
+    }
+
+    /// Return true if the command code or an alias of this invocable starts with the given prefix, ignoring case.
+    pub fn has_code_prefix(&self, prefix: &str) -> bool {
+        let prefix = prefix.to_ascii_lowercase();
+        self.codes().any(|c| c.to_ascii_lowercase().starts_with(&prefix))
+    }
+
     /// Construct and return a default invocable.
     fn base(
         command_code: &'static str,
@@ -68,13 +201,7 @@ impl Invocable {
             command_code: command_code.to_string(),
             command: command.to_string(),
             description: description.to_string(),
-            use_cmd: false,
-            use_start: false,
-            background: false,
-            use_call: false,
-            use_explorer: false,
-            use_bash: false,
-            arguments: vec![],
+            ..Default::default()
         };
 
         for arg in args.iter() {
@@ -114,7 +241,7 @@ impl Invocable {
         args: &[&str],
     ) -> Invocable {
         let mut inv = Invocable::base(command_code, command, description, args);
-        inv.use_explorer = true;
+        inv.mode = LaunchMode::Explorer;
         inv
     }
 
@@ -137,7 +264,7 @@ impl Invocable {
         args: &[&str],
     ) -> Invocable {
         let mut inv = Invocable::base(command_code, command, description, args);
-        inv.background = true;
+        inv.mode = LaunchMode::CmdStartBackground { slash_c: false };
         inv
     }
 
@@ -160,7 +287,7 @@ impl Invocable {
         args: &[&str],
     ) -> Invocable {
         let mut inv = Invocable::base(command_code, command, description, args);
-        inv.use_cmd = true;
+        inv.mode = LaunchMode::Cmd { wait: true };
         inv
     }
 
@@ -183,7 +310,7 @@ impl Invocable {
         args: &[&str],
     ) -> Invocable {
         let mut inv = Invocable::base(command_code, command, description, args);
-        inv.use_bash = true;
+        inv.mode = LaunchMode::Bash;
         inv
     }
 
@@ -4,154 +4,618 @@
 
 //TODO: is there a better way to reference the get_config_file_path function and the InvocableCategory struct?
 
-use crate::wsl::get_config_file_path;
+use crate::config::configerror::ConfigError;
+use crate::config::configinclude::ConfigInclude;
+use crate::config::configloader::ConfigLoader;
+use crate::config::configsource::ConfigSource;
+use crate::config::migration::UNVERSIONED_FORMAT_VERSION;
+use crate::suggest::{close_distance, closest};
 use crate::wsl::inv::invocable::Invocable;
 use crate::wsl::inv::invocablecategory::InvocableCategory;
 
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+/// A wink configuration file, or the list of categories that wink exports with -e.
+#[derive(serde::Serialize, serde::Deserialize, schemars::JsonSchema, Debug, Clone)]
+#[serde(deny_unknown_fields)]
 pub struct InvocableCategoryList {
+    /// The JSON Schema for editors to use to validate and complete the file, such as one exported with wink -s.
+    #[serde(rename = "$schema", default, skip_serializing_if = "String::is_empty")]
+    pub schema: String,
+
+    /// The version of the configuration file format, which wink uses to upgrade files written for earlier versions.
+    #[serde(default = "unversioned")]
+    pub format_version: u32,
+
+    /// Other configuration files to apply before this one, such as shared command packs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub includes: Vec<ConfigInclude>,
+
     /// The categories field contains the list of InvocableCategory.
+    #[serde(default)]
     pub categories: Vec<InvocableCategory>,
+
+    /// Command codes of invocables to remove from wink and from the configuration files applied before this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_codes: Vec<String>,
+
+    /// Names of categories to remove from wink and from the configuration files applied before this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_categories: Vec<String>,
+
+    /// The configuration files applied to this list, in the order applied.
+    #[serde(skip)]
+    pub sources: Vec<ConfigSource>,
+
+    /// The problems with the configuration files that wink ignored while building this list.
+    #[serde(skip)]
+    pub errors: Vec<ConfigError>,
+}
+
+/// The built-in catalog, which build.rs writes to OUT_DIR.
+const CATALOG: &str = include_str!(concat!(env!("OUT_DIR"), "/wink.json"));
+
+/// The names, cargo features, and command codes of the built-in categories that this build excludes,
+/// such as ("Shutdown", "cat-shutdown", &["boot", ...]) when built without the cat-shutdown feature.
+pub const EXCLUDED_CATEGORIES: &[(&str, &str, &[&str])] = include!(concat!(env!("OUT_DIR"), "/excluded.rs"));
+
+/// Return the name of the built-in category that this build excludes with the given name or command code,
+/// ignoring case, if any.
+pub fn excluded_category(name_or_code: &str) -> Option<&'static str> {
+    EXCLUDED_CATEGORIES
+        .iter()
+        .find(|(name, _feature, codes)| {
+            name.eq_ignore_ascii_case(name_or_code) || codes.iter().any(|c| c.eq_ignore_ascii_case(name_or_code))
+        })
+        .map(|(name, _feature, _codes)| *name)
+}
+
+/// The most suggestions for an unrecognized command code.
+pub const SUGGESTION_LIMIT: usize = 5;
+
+/// Scores of suggestions with command codes or aliases that contain the unrecognized command code or that it contains,
+/// which rank after misspellings, and of suggestions with descriptions that contain it, which rank last.
+const SUBSTRING_SCORE: usize = 10;
+const DESCRIPTION_SCORE: usize = 100;
+
+/// Return a message for a disabled category or command code that does not exist, suggesting the closest that does.
+fn unknown(kind: &str, name: &str, existing: &[&str]) -> String {
+    match closest(name, existing) {
+        Some(suggestion) => format!("Cannot disable unknown {0} {1}, did you mean {2}?", kind, name, suggestion),
+        None => format!("Cannot disable unknown {0} {1}", kind, name),
+    }
+}
+
+/// Return a message for a command code or alias that invocables in two categories define, in the given file.
+fn duplicate(code: &str, first: &str, second: &str, path: &str) -> String {
+    format!("Command code {0} defined for both {1} and {2} in {3}", code, first, second, path)
+}
+
+/// Return a message for a command code or alias, defined for an invocable in the given category in the given file,
+/// that is reserved for a command that wink runs itself.
+fn reserved(code: &str, category: &str, path: &str) -> String {
+    format!("Command code {0} defined for {1} in {2} is reserved for wink {0} and cannot be invoked", code, category, path)
+}
+
+/// Documents without a format_version predate versioning.
+fn unversioned() -> u32 {
+    UNVERSIONED_FORMAT_VERSION
 }
 
 impl InvocableCategoryList {
+    /// Return the invocable with the given command code, or otherwise with the given alias, ignoring case, if any.
     pub fn get_invocable(&self, command_code: &str) -> Option<&Invocable> {
-        self.categories
-            .iter()
-            .flat_map(|c| c.invocables.iter())
-            .find(|i| i.command_code == command_code)
+        let mut invocables = self.categories.iter().flat_map(|c| c.invocables.iter());
+
+        invocables
+            .clone()
+            .find(|i| i.command_code.eq_ignore_ascii_case(command_code))
+            .or_else(|| invocables.find(|i| i.has_code(command_code)))
     }
 
-    /// Return an InvocableCategoryList populated from a hard-coded list of categories
-    /// plus the contents of $HOME/.wink.json (WSL) or $USERPROFILE/wink.json (Windows).
-    pub fn get() -> InvocableCategoryList {
-        let mut category_list = InvocableCategoryList {
-            categories: Vec::new(),
-        };
+    /// Return the invocable with the given command code or alias, ignoring case,
+    /// or otherwise the only invocable with a command code or alias that starts with it, such as procm for procmon.
+    pub fn find_invocable(&self, command_code: &str) -> Option<&Invocable> {
+        if let Some(invocable) = self.get_invocable(command_code) {
+            return Some(invocable);
+        }
 
-        //TODO: convert to a list of names and delegate methods
+        match self.candidates(command_code).as_slice() {
+            [(_category, invocable)] => Some(invocable),
+            _ => None,
+        }
+    }
 
-        let mut screensavers = InvocableCategory::new("Screen Savers");
-        screensavers.add_screensavers();
-        category_list.categories.push(screensavers);
+    /// Return up to SUGGESTION_LIMIT categories and invocables for an unrecognized command code, best first,
+    /// with scores that are lower for better suggestions: the edit distance to the closest command code or alias
+    /// for likely misspellings, then command codes and aliases that contain the code or that it contains,
+    /// then descriptions that contain it, ignoring case.
+    pub fn suggestions(&self, command_code: &str) -> Vec<(&InvocableCategory, &Invocable, usize)> {
+        let word = command_code.to_lowercase();
+        let mut suggestions: Vec<(&InvocableCategory, &Invocable, usize)> = vec![];
 
-        let mut applications = InvocableCategory::new("Applications");
-        applications.add_applications();
-        category_list.categories.push(applications);
+        for category in self.categories.iter() {
+            for invocable in category.invocables.iter() {
+                let mut scores: Vec<usize> = vec![];
 
-        let mut locations = InvocableCategory::new("Locations");
-        locations.add_locations();
-        category_list.categories.push(locations);
+                for code in invocable.codes() {
+                    let code = code.to_lowercase();
+                    scores.extend(close_distance(&word, &code));
 
-        let mut settings = InvocableCategory::new("Settings");
-        settings.add_settings();
-        category_list.categories.push(settings);
+                    if code.contains(&word) || (code.len() > 2 && word.contains(&code)) {
+                        scores.push(SUBSTRING_SCORE + code.len().abs_diff(word.len()));
+                    }
+                }
 
-        let mut sysinternals = InvocableCategory::new("Sysinternals.com");
-        sysinternals.add_sysinternals();
-        category_list.categories.push(sysinternals);
+                if word.len() > 2 && invocable.description.to_lowercase().contains(&word) {
+                    scores.push(DESCRIPTION_SCORE);
+                }
 
-        let mut networking = InvocableCategory::new("Networking");
-        networking.add_networking();
-        category_list.categories.push(networking);
+                if let Some(score) = scores.into_iter().min() {
+                    suggestions.push((category, invocable, score));
+                }
+            }
+        }
 
-        let mut privacy = InvocableCategory::new("Privacy");
-        privacy.add_privacy();
-        category_list.categories.push(privacy);
+        suggestions.sort_by_key(|(_category, _invocable, score)| *score);
+        suggestions.truncate(SUGGESTION_LIMIT);
+        suggestions
+    }
 
-        let mut ease_of_access = InvocableCategory::new("Ease of Access");
-        ease_of_access.add_ease_of_access();
-        category_list.categories.push(ease_of_access);
+    /// Return the categories and invocables that match every search term, ignoring case, best first, with their scores:
+    /// the sum, for each term, of the score of the best field that it matches, from command codes and aliases
+    /// that equal the term to commands and arguments that contain it, breaking ties by category name and command code.
+    pub fn search(&self, terms: &[String]) -> Vec<(&InvocableCategory, &Invocable, usize)> {
+        let terms: Vec<String> = terms.iter().map(|t| t.to_lowercase()).filter(|t| !t.is_empty()).collect();
+        let mut matches: Vec<(&InvocableCategory, &Invocable, usize)> = vec![];
 
-        let mut security = InvocableCategory::new("Security");
-        security.add_security();
-        category_list.categories.push(security);
+        if terms.is_empty() {
+            return matches;
+        }
 
-        let mut linux = InvocableCategory::new("Linux");
-        linux.add_linux();
-        category_list.categories.push(linux);
+        for category in self.categories.iter() {
+            for invocable in category.invocables.iter() {
+                let codes: Vec<String> = invocable.codes().map(|c| c.to_lowercase()).collect();
+                let tags: Vec<String> = invocable.tags.iter().map(|t| t.to_lowercase()).collect();
+                let mut total = 0;
 
-        let mut miscellaneous = InvocableCategory::new("Miscelaneous");
-        miscellaneous.add_miscellaneous();
-        category_list.categories.push(miscellaneous);
+                for term in terms.iter() {
+                    // the scores of the fields that the term can match, best first
+                    let fields = [
+                        (100, codes.iter().any(|c| c == term)),
+                        (60, codes.iter().any(|c| c.starts_with(term.as_str()))),
+                        (50, tags.iter().any(|t| t == term)),
+                        (40, codes.iter().any(|c| c.contains(term.as_str()))),
+                        (30, tags.iter().any(|t| t.contains(term.as_str()))),
+                        (20, invocable.description.to_lowercase().contains(term.as_str())),
+                        (15, invocable.keywords.to_lowercase().contains(term.as_str())),
+                        (10, category.name.to_lowercase().contains(term.as_str())),
+                        (5, invocable.command.to_lowercase().contains(term.as_str())),
+                        (5, invocable.arguments.iter().any(|a| a.to_lowercase().contains(term.as_str()))),
+                    ];
 
-        let mut features = InvocableCategory::new("Features");
-        features.add_features();
-        category_list.categories.push(features);
+                    match fields.iter().find(|(_score, matched)| *matched) {
+                        Some((score, _matched)) => total += score,
+                        None => {
+                            total = 0;
+                            break;
+                        }
+                    }
+                }
 
-        let mut shutdown = InvocableCategory::new("Shutdown");
-        shutdown.add_shutdown();
-        category_list.categories.push(shutdown);
+                if total > 0 {
+                    matches.push((category, invocable, total));
+                }
+            }
+        }
 
-        let mut utilities = InvocableCategory::new("Utilities");
-        utilities.add_utilities();
-        category_list.categories.push(utilities);
+        matches.sort_by(|(c1, i1, s1), (c2, i2, s2)| {
+            s2.cmp(s1).then_with(|| c1.name.cmp(&c2.name)).then_with(|| i1.command_code.cmp(&i2.command_code))
+        });
+        matches
+    }
 
-        let mut office = InvocableCategory::new("Microsoft Office");
-        office.add_office();
-        category_list.categories.push(office);
+    /// Return the best suggestion for an unrecognized command code if no other suggestion is as good, if any.
+    pub fn best_suggestion(&self, command_code: &str) -> Option<&Invocable> {
+        match self.suggestions(command_code).as_slice() {
+            [(_category, invocable, _score)] => Some(invocable),
+            [(_c1, invocable, best), (_c2, _i2, next), ..] if best < next => Some(invocable),
+            _ => None,
+        }
+    }
 
-        let path: String = get_config_file_path("wink.json");
+    /// Return the categories and invocables with a command code or alias that starts with the given prefix, ignoring case.
+    pub fn candidates(&self, prefix: &str) -> Vec<(&InvocableCategory, &Invocable)> {
+        self.categories
+            .iter()
+            .flat_map(|c| c.invocables.iter().map(move |i| (c, i)))
+            .filter(|(_category, invocable)| invocable.has_code_prefix(prefix))
+            .collect()
+    }
 
-        if std::path::Path::new(&path).exists() {
-            // if the path exists, then propagate all errors, so OK to unwrap from here
-            let data = std::fs::read_to_string(&path)
-                .unwrap_or_else(|_| panic!("Unable to read {0}", &path));
-            let deserialized: InvocableCategoryList = serde_json::from_str(&data).unwrap();
+    /// Return an InvocableCategoryList populated from a hard-coded list of categories
+    /// overridden by the system, user, and project configuration files
+    /// such as $HOME/.wink.json (WSL) or $USERPROFILE/wink.json (Windows).
+    /// Write any problems with those files to stderr and ignore the files that have them.
+    pub fn get() -> InvocableCategoryList {
+        let mut category_list = InvocableCategoryList::built_in();
 
-            //TODO: replace following check to update hard-coded categories with anything from the configuration file.
+        category_list.errors = ConfigLoader::discover().load(&mut category_list);
 
-            // if any category already exists, it will appear twice; report it.
-            for category in deserialized.categories.iter() {
-                for existing in category_list.categories.iter() {
-                    if category.name == existing.name {
-                        eprintln!(
-                            "Category {0} defined in multiple places including {1}",
-                            category.name, path
-                        );
-                    }
+        for error in category_list.errors.iter() {
+            eprintln!("Ignoring configuration file {0}", error);
+        }
+
+        category_list
+    }
+
+    /// Merge the categories from a configuration file into this list.
+    /// First remove the categories and invocables that the file disables.
+    /// Categories merge by name. Each invocable replaces any existing invocable with the same command code, ignoring case,
+    /// in place if in the same category, and otherwise by moving to the new category.
+    pub fn merge(&mut self, document: InvocableCategoryList, source: &ConfigSource) {
+        for error in self.disable(&document.disabled_codes, &document.disabled_categories) {
+            eprintln!("{0} in {1}", error, source.path);
+        }
+
+        for collision in self.collisions(&document, &source.path) {
+            eprintln!("{}", collision);
+        }
+
+        for category in document.categories {
+            let index = match self.categories.iter().position(|c| c.name == category.name) {
+                Some(index) => index,
+                None => {
+                    self.categories.push(InvocableCategory {
+                        name: category.name.clone(),
+                        invocables: vec![],
+                    });
+                    self.categories.len() - 1
                 }
+            };
 
-                // if any command code exists twice within a category, report it.
-                // subsequent logic will check for connflicts accross categories.
-                let mut existing: Vec<&str> = vec![];
+            for mut invocable in category.invocables {
+                invocable.source = source.clone();
 
-                for invocable in category.invocables.iter() {
-                    if existing.contains(&invocable.command_code.as_str()) {
-                        eprintln!("Command code {0} defined multiple places including {1} category of {2}", &invocable.command_code, &category.name, path);
-                    } else {
-                        existing.push(&invocable.command_code);
-                    }
+                if let Some(existing) = self.categories[index]
+                    .invocables
+                    .iter_mut()
+                    .find(|i| i.command_code.eq_ignore_ascii_case(&invocable.command_code))
+                {
+                    *existing = invocable;
+                    continue;
+                }
+
+                for other in self.categories.iter_mut() {
+                    other
+                        .invocables
+                        .retain(|i| !i.command_code.eq_ignore_ascii_case(&invocable.command_code));
                 }
 
-                // add the deserialized list to the list in memory
-                category_list.categories.push(category.to_owned());
+                self.categories[index].invocables.push(invocable);
             }
+        }
 
-            // report duplicate command codes
-            for category in category_list.categories.iter() {
-                for invocable in category.invocables.iter() {
-                    for compcat in category_list.categories.iter() {
-                        if compcat.name != category.name {
-                            for compinv in compcat.invocables.iter() {
-                                if invocable.command_code == compinv.command_code {
-                                    //TODO: enhance to error messages to show command line or at least wink command name
-                                    eprintln!(
-                                        "Command code {0} defined for both {1} {2} and {3} {4}",
-                                        invocable.command_code,
-                                        category.name,
-                                        invocable.command,
-                                        compcat.name,
-                                        compinv.command
-                                    );
-                                }
-                            }
-                        }
+        self.sources.push(source.clone());
+    }
+
+    /// Remove the invocables with the given command codes and the categories with the given names
+    /// from this list, and return a message for each code or name that this list does not contain,
+    /// except those of built-in categories that this build excludes.
+    pub fn disable(&mut self, codes: &[String], category_names: &[String]) -> Vec<String> {
+        let mut errors: Vec<String> = vec![];
+
+        for name in category_names.iter() {
+            if self.categories.iter().any(|c| c.name == *name) {
+                self.categories.retain(|c| c.name != *name);
+            } else if excluded_category(name).is_none() {
+                let names: Vec<&str> = self.categories.iter().map(|c| c.name.as_str()).collect();
+                errors.push(unknown("category", name, &names));
+            }
+        }
+
+        for code in codes.iter() {
+            if self.get_invocable(code).is_some() {
+                for category in self.categories.iter_mut() {
+                    category.invocables.retain(|i| !i.has_code(code));
+                }
+            } else if excluded_category(code).is_none() {
+                let existing: Vec<&str> = self
+                    .categories
+                    .iter()
+                    .flat_map(|c| c.invocables.iter())
+                    .flat_map(|i| i.codes())
+                    .map(|c| c.as_str())
+                    .collect();
+                errors.push(unknown("command code", code, &existing));
+            }
+        }
+
+        errors
+    }
+
+    /// Return a message for each command code or alias in the document, from the file at the given path,
+    /// that is also a code or alias of an invocable from an earlier layer in this list that the document does not replace,
+    /// or that is reserved for a command that wink runs itself, such as config.
+    pub fn collisions(&self, document: &InvocableCategoryList, path: &str) -> Vec<String> {
+        let mut messages: Vec<String> = vec![];
+        let added: Vec<(&str, &Invocable)> = document
+            .categories
+            .iter()
+            .flat_map(|c| c.invocables.iter().map(move |i| (c.name.as_str(), i)))
+            .collect();
+
+        for (name, invocable) in added.iter() {
+            for code in invocable.codes().filter(|c| crate::is_reserved(c)) {
+                messages.push(reserved(code, name, path));
+            }
+        }
+
+        for category in self.categories.iter() {
+            for existing in category.invocables.iter() {
+                if added.iter().any(|(_name, i)| i.command_code.eq_ignore_ascii_case(&existing.command_code)) {
+                    continue;
+                }
+
+                for (name, invocable) in added.iter() {
+                    for code in invocable.codes().filter(|c| existing.has_code(c)) {
+                        messages.push(duplicate(code, &category.name, name, path));
                     }
                 }
             }
         }
 
-        category_list
+        messages
+    }
+
+    /// Write to stderr for each command code or alias defined more than once in this list,
+    /// such as in a single configuration file at the given path.
+    pub fn report_duplicates(&self, path: &str) {
+        let mut existing: Vec<(&str, &str)> = vec![];
+
+        for category in self.categories.iter() {
+            for code in category.invocables.iter().flat_map(|i| i.codes()) {
+                if let Some((_code, name)) = existing.iter().find(|(c, _name)| c.eq_ignore_ascii_case(code)) {
+                    eprintln!("{}", duplicate(code, name, &category.name, path));
+                } else {
+                    existing.push((code, &category.name));
+                }
+            }
+        }
+    }
+
+    /// Return an InvocableCategoryList populated from the built-in catalog in src/wsl/inv/catalog.toml,
+    /// which build.rs validates and converts to the form that wink exports.
+    pub fn built_in() -> InvocableCategoryList {
+        serde_json::from_str(CATALOG).expect("build.rs validates the built-in catalog")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::configsource::{ConfigLayer, ConfigSource};
+    use crate::wsl::inv::invocable::Invocable;
+    use crate::wsl::inv::invocablecategory::InvocableCategory;
+    use crate::wsl::inv::invocablecategorylist::{excluded_category, InvocableCategoryList};
+
+    fn document(name: &'static str, invocables: Vec<Invocable>) -> InvocableCategoryList {
+        let mut category = InvocableCategory::new(name);
+        category.invocables = invocables;
+        InvocableCategoryList {
+            schema: String::new(),
+            format_version: crate::config::migration::FORMAT_VERSION,
+            includes: vec![],
+            categories: vec![category],
+            disabled_codes: vec![],
+            disabled_categories: vec![],
+            sources: vec![],
+            errors: vec![],
+        }
+    }
+
+    #[test]
+    fn it_replaces_invocables_by_command_code() {
+        let mut category_list = InvocableCategoryList::built_in();
+        let source = ConfigSource::new(ConfigLayer::User, "/home/jw/.wink.json");
+        category_list.merge(
+            document("Microsoft Office", vec![Invocable::bin("word", "/bin/true", "Word")]),
+            &source,
+        );
+
+        let word = category_list.get_invocable("word").unwrap();
+        assert_eq!(word.command, "/bin/true");
+        assert_eq!(word.source, source);
+        assert_eq!(
+            category_list
+                .categories
+                .iter()
+                .filter(|c| c.name == "Microsoft Office")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn it_reports_codes_that_collide_with_earlier_layers() {
+        let mut vscode = Invocable::bin("vscode", "code.exe", "Visual Studio Code");
+        vscode.aliases = vec!["vsc".to_string()];
+        let category_list = document("Applications", vec![vscode.clone()]);
+        let path = "/home/jw/.wink.json";
+
+        let collisions = category_list.collisions(&document("Mine", vec![Invocable::bin("VSC", "/bin/true", "Mine")]), path);
+        assert_eq!(collisions, vec!["Command code VSC defined for both Applications and Mine in /home/jw/.wink.json"]);
+
+        let mut code = Invocable::bin("code", "/bin/true", "Mine");
+        code.aliases = vec!["vsc".to_string()];
+        assert_eq!(category_list.collisions(&document("Mine", vec![code]), path).len(), 1);
+
+        // replacing an invocable with the same command code is not a collision
+        assert!(category_list.collisions(&document("Mine", vec![vscode]), path).is_empty());
+
+        // wink runs its own commands, such as wink search, before looking up invocables
+        let mut find = Invocable::bin("Search", "/bin/grep", "Mine");
+        find.aliases = vec!["find".to_string(), "doctor".to_string()];
+        assert_eq!(
+            category_list.collisions(&document("Mine", vec![find]), path),
+            vec![
+                "Command code Search defined for Mine in /home/jw/.wink.json is reserved for wink Search and cannot be invoked",
+                "Command code doctor defined for Mine in /home/jw/.wink.json is reserved for wink doctor and cannot be invoked",
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "cat-office")]
+    fn it_moves_invocables_between_categories() {
+        let mut category_list = InvocableCategoryList::built_in();
+        let source = ConfigSource::new(ConfigLayer::Project, "/tmp/.wink.json");
+        category_list.merge(
+            document("Project", vec![Invocable::bin("word", "/bin/true", "Word")]),
+            &source,
+        );
+
+        let count = category_list
+            .categories
+            .iter()
+            .flat_map(|c| c.invocables.iter())
+            .filter(|i| i.command_code == "word")
+            .count();
+        assert_eq!(count, 1);
+        assert_eq!(category_list.categories.last().unwrap().name, "Project");
+        assert!(category_list.get_invocable("excel").unwrap().source.is_built_in());
+    }
+
+    #[test]
+    #[cfg(feature = "cat-applications")]
+    fn it_resolves_aliases() {
+        let mut category_list = InvocableCategoryList::built_in();
+        assert_eq!(category_list.get_invocable("vsc").unwrap().command_code, "vscode");
+
+        // a command code takes precedence over an alias of another invocable
+        let mut code = Invocable::bin("code", "/bin/true", "Code");
+        code.aliases = vec!["vscode".to_string()];
+        let source = ConfigSource::new(ConfigLayer::User, "/home/jw/.wink.json");
+        category_list.merge(document("Mine", vec![code]), &source);
+        assert_eq!(category_list.get_invocable("code").unwrap().command, "/bin/true");
+        assert_eq!(category_list.get_invocable("vscode").unwrap().description, "Microsoft Visual Studio Code");
+
+        let exported = serde_json::to_string(category_list.get_invocable("code").unwrap()).unwrap();
+        let imported: Invocable = serde_json::from_str(&exported).unwrap();
+        assert_eq!(imported.aliases, vec!["vscode"]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "cat-office", feature = "cat-sysinternals", feature = "cat-applications"))]
+    fn it_matches_case_and_prefixes() {
+        let category_list = InvocableCategoryList::built_in();
+        assert_eq!(category_list.get_invocable("WORD").unwrap().command_code, "word");
+        assert_eq!(category_list.find_invocable("procm").unwrap().command_code, "procmon");
+        assert_eq!(category_list.find_invocable("VSC").unwrap().command_code, "vscode");
+        assert!(category_list.get_invocable("procm").is_none());
+
+        // an exact match takes precedence over longer codes with the same prefix
+        assert_eq!(category_list.find_invocable("vs").unwrap().command_code, "vs");
+
+        assert!(category_list.find_invocable("proc").is_none());
+        let candidates: Vec<&str> = category_list
+            .candidates("proc")
+            .iter()
+            .map(|(_category, invocable)| invocable.command_code.as_str())
+            .collect();
+        assert_eq!(candidates, vec!["procexp", "procmon"]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "cat-office", feature = "cat-networking", feature = "cat-utilities", feature = "cat-settings"))]
+    fn it_suggests_invocables_for_unrecognized_codes() {
+        let category_list = InvocableCategoryList::built_in();
+        let suggestions = category_list.suggestions("wrod");
+        assert_eq!(suggestions[0].1.command_code, "word");
+        assert_eq!(category_list.best_suggestion("wrod").unwrap().command_code, "word");
+        assert_eq!(category_list.best_suggestion("EXEL").unwrap().command_code, "excel");
+
+        // descriptions match after command codes and aliases, and many matches leave no best suggestion
+        assert_eq!(category_list.best_suggestion("bluetooth").unwrap().command_code, "bluetoo");
+        assert!(category_list.suggestions("bluetooth").iter().any(|(_c, invocable, _s)| invocable.command_code == "fsquirt"));
+        assert_eq!(category_list.suggestions("panel").len(), crate::wsl::inv::invocablecategorylist::SUGGESTION_LIMIT);
+        assert!(category_list.best_suggestion("panel").is_none());
+        assert!(category_list.suggestions("zzzzqq").is_empty());
+    }
+
+    #[test]
+    #[cfg(all(feature = "cat-privacy", feature = "cat-security", feature = "cat-office"))]
+    fn it_searches_codes_descriptions_tags_and_keywords() {
+        let category_list = InvocableCategoryList::built_in();
+        let terms = |terms: &[&str]| terms.iter().map(|t| t.to_string()).collect::<Vec<String>>();
+        let codes = |terms: &[&str]| -> Vec<String> {
+            category_list
+                .search(&terms.iter().map(|t| t.to_string()).collect::<Vec<String>>())
+                .iter()
+                .map(|(_category, invocable, _score)| invocable.command_code.clone())
+                .collect()
+        };
+
+        // every term must match, and a command code that equals a term ranks first
+        let matches = category_list.search(&terms(&["privacy", "MS-SETTINGS"]));
+        assert_eq!(matches[0].1.command_code, "privacy");
+        assert!(matches.iter().all(|(_category, invocable, _score)| invocable.tags.contains(&"ms-settings".to_string())));
+
+        assert!(codes(&["mmc"]).contains(&"gpedit".to_string()));
+        assert_eq!(codes(&["spreadsheet"]), vec!["excel"]);
+        assert!(codes(&["spreadsheet", "zzzzqq"]).is_empty());
+        assert!(codes(&[]).is_empty());
+    }
+
+    #[test]
+    #[cfg(all(feature = "cat-linux", feature = "cat-shutdown"))]
+    fn it_disables_invocables_and_categories() {
+        let mut category_list = InvocableCategoryList::built_in();
+        let mut disabling = document("Mine", vec![Invocable::bin("shutdown", "/bin/true", "Mine")]);
+        disabling.disabled_codes = vec!["wnice".to_string(), "wince".to_string()];
+        disabling.disabled_categories = vec!["Shutdown".to_string(), "Nonexistent".to_string()];
+
+        let errors = category_list.disable(&disabling.disabled_codes, &disabling.disabled_categories);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("Cannot disable unknown category Nonexistent"));
+        assert_eq!(errors[1], "Cannot disable unknown command code wnice, did you mean wince?");
+        assert!(category_list.get_invocable("wince").is_none());
+        assert!(!category_list.categories.iter().any(|c| c.name == "Shutdown"));
+
+        // a configuration file can disable a built-in invocable and define its own with the same code
+        let mut category_list = InvocableCategoryList::built_in();
+        category_list.merge(disabling, &ConfigSource::new(ConfigLayer::User, "/home/jw/.wink.json"));
+        assert_eq!(category_list.get_invocable("shutdown").unwrap().command, "/bin/true");
+        assert!(category_list.get_invocable("wince").is_none());
+    }
+
+    #[test]
+    #[cfg(not(feature = "cat-shutdown"))]
+    fn it_names_excluded_categories_ignoring_case() {
+        assert_eq!(excluded_category("BOOT"), Some("Shutdown"));
+        assert_eq!(excluded_category("shutdown"), Some("Shutdown"));
+        assert_eq!(excluded_category("zzzzqq"), None);
+    }
+
+    #[test]
+    fn it_matches_the_baseline_catalog() {
+        // the export of the catalog before wink moved it to catalog.toml, in the unversioned format
+        let mut baseline: serde_json::Value = serde_json::from_str(include_str!("../../../tests/fixtures/baseline-wink.json")).unwrap();
+        crate::config::migration::migrate(&mut baseline).unwrap();
+        let mut baseline: InvocableCategoryList = serde_json::from_value(baseline).unwrap();
+        let mut built_in = InvocableCategoryList::built_in();
+
+        // builds without some category features exclude those categories
+        baseline.categories.retain(|c| excluded_category(&c.name).is_none());
+
+        // later versions add aliases, argument kinds, tags, and keywords, but change nothing else
+        for invocable in built_in.categories.iter_mut().flat_map(|c| c.invocables.iter_mut()) {
+            invocable.aliases.clear();
+            invocable.argument_kind = Default::default();
+            invocable.tags.clear();
+            invocable.keywords.clear();
+        }
+
+        assert_eq!(
+            serde_json::to_value(&baseline).unwrap(),
+            serde_json::to_value(&built_in).unwrap()
+        );
     }
 }
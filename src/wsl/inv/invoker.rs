@@ -5,11 +5,20 @@
 //TODO: is this the best way to reference the Invocable struct and the wsl_path_or_self() function?
 
 use crate::wsl::inv::invocable::Invocable;
+use crate::wsl::inv::launchmode::LaunchMode;
+use crate::wsl::inv::tokenvalues::TokenValues;
 use crate::wsl::wsl_path_or_self;
 
 pub struct Invoker {}
 
 impl Invoker {
+    /// Return the command of the given invocable with its tokens replaced
+    /// and converted to a path for the current platform, which is the executable that wink invokes
+    /// for invocables that do not use cmd.exe, explorer.exe, or bash.exe.
+    pub fn resolve_command(invocable: &Invocable, tokens: &TokenValues) -> String {
+        wsl_path_or_self(&tokens.replace(&invocable.command), !cfg!(target_os = "windows"))
+    }
+
     /// This function creates a command line from the specified invocable and args,
     /// writes that command line to stdout if verbose is true,
     /// and invokes that command line.
@@ -21,73 +30,64 @@ impl Invoker {
         verbose: bool,
         args: Vec<String>,
     ) -> String {
-        // create three constants for substituting tokens in command paths
-        let results = std::process::Command::new("cmd.exe")
-            .arg("/c")
-            .arg("echo")
-            .arg("%USERPROFILE%")
-            .output()
-            .expect("failed to execute process");
-        let userpath: String = match results.status.code() {
-            Some(0) => wsl_path_or_self(String::from_utf8_lossy(&results.stdout).trim(), false),
-            _ => String::new(),
-        };
-
-        let results = std::process::Command::new("cmd.exe")
-            .arg("/c")
-            .arg("echo")
-            .arg("%ProgramFiles%")
-            .output()
-            .expect("failed to execute process");
-        let pf64: String = match results.status.code() {
-            Some(0) => wsl_path_or_self(String::from_utf8_lossy(&results.stdout).trim(), false),
-            _ => String::new(),
-        };
-
-        let results = std::process::Command::new("cmd.exe")
-            .arg("/c")
-            .arg("echo")
-            .arg("%ProgramFiles(x86)%")
-            .output()
-            .expect("failed to execute process");
-        let pf86 = match results.status.code() {
-            Some(0) => wsl_path_or_self(String::from_utf8_lossy(&results.stdout).trim(), false),
-            _ => String::new(),
-        };
+        // the values for substituting tokens in command paths
+        self.invoke_with(invocable, &TokenValues::get(), dry_run, verbose, args)
+    }
+
+    /// Like invoke(), but with token values that the caller already has, such as help that also shows them,
+    /// because getting them starts cmd.exe.
+    pub fn invoke_with(
+        &self,
+        invocable: &Invocable,
+        tokens: &TokenValues,
+        dry_run: bool,
+        verbose: bool,
+        args: Vec<String>,
+    ) -> String {
+        let (mut torun, command_line) = Invoker::command(invocable, tokens, &args);
+
+        if verbose {
+            println!("{}", command_line);
+        }
+
+        if !dry_run {
+            if let LaunchMode::CmdStartBackground { .. } = invocable.mode {
+                let _discard = torun.status();
+            } else {
+                let results = torun.output().expect("failed to execute process");
+                let err = String::from_utf8_lossy(&results.stderr);
+
+                if !err.is_empty() {
+                    eprintln!("{}", err);
+                }
+
+                let out = String::from_utf8_lossy(&results.stdout);
+
+                if !out.is_empty() {
+                    println!("{}", out);
+                }
+            }
+        }
 
+        command_line
+    }
+
+    /// Return the Command that invokes the given invocable with the given args, converting paths with wslpath,
+    /// and the command line that -v writes for it, which the picker also previews.
+    pub fn command(invocable: &Invocable, tokens: &TokenValues, args: &[String]) -> (std::process::Command, String) {
         // for -v [verbose] command line option
         let mut command_line = String::new();
 
-        // the command to invoke - cmd.exe, explorer.exe, or the executable specified in the command field of the invocable.
-        let cmd: &str;
-
         // if directed to use cmd.exe or start or start /b, then use cmd.exe /c
         // else if directed to use explorer.exe, then use explorer.exe
         // otherwise invoke the executable directly
         // this would be the executable to invoke
         //TODO: create maybe_executable in else block below instead of here; maybe requires cmd to be String?
-        let maybe_executable = &wsl_path_or_self(
-            &invocable
-                .command
-                .replace("$pf64", &pf64)
-                .replace("$pf86", &pf86)
-                .replace("$userpath", &userpath)
-                .replace("$syslive", "\\\\live.sysinternals.com\\tools\\"),
-            !cfg!(target_os = "windows"),
-        );
+        let maybe_executable = &Invoker::resolve_command(invocable, tokens);
 
-        // if directed to use cmd.exe or start or start /b, then use cmd.exe /c
-        // else if directed to use explorer.exe, then use explorer.exe
+        // if directed to use cmd.exe, explorer.exe, or bash.exe, then use that program
         // otherwise invoke the executable directly
-        if invocable.use_cmd || invocable.use_start || invocable.background {
-            cmd = "cmd.exe";
-        } else if invocable.use_bash {
-            cmd = "bash.exe"
-        } else if invocable.use_explorer {
-            cmd = "explorer.exe";
-        } else {
-            cmd = &maybe_executable;
-        }
+        let cmd: &str = invocable.mode.program().unwrap_or(maybe_executable);
 
         command_line.push_str(cmd);
         command_line.push(' ');
@@ -95,58 +95,19 @@ impl Invoker {
         // the Command object to invoke the command line
         let mut torun = std::process::Command::new(String::from(cmd));
 
-        // /wait and /c for cmd.exe
-        if invocable.use_cmd {
-            if !invocable.background {
-                torun.arg("/wait");
-                command_line.push_str("/wait ");
-            }
-
-            torun.arg("/c");
-            command_line.push_str("/c ");
-        }
-
-        if invocable.use_bash {
-            //TODO:  if invocable.background
-            torun.arg("-c");
-            command_line.push_str("-c ");
-        }
-
-        // both start and start /b require start
-        if invocable.use_start || invocable.background {
-            torun.arg("start");
-            command_line.push_str("start ");
+        // such as /wait /c start /b for cmd.exe or -c for bash.exe
+        //TODO: background for bash.exe
+        for arg in invocable.mode.program_arguments() {
+            torun.arg(arg);
+            command_line.push_str(arg);
+            command_line.push(' ');
         }
 
-        // start /b
-        if invocable.background {
-            torun.arg("/b");
-            command_line.push_str("/b ");
-        };
-
-        // untested; runs without creating a new environment?
-        if invocable.use_call {
-            torun.arg("call");
-            command_line.push_str("call ");
-        }
+        let use_bash = invocable.mode == LaunchMode::Bash;
 
         // if executable specified with cmd.exe then add windows path to executable to command line
-        if (invocable.use_cmd
-            || invocable.use_start
-            || invocable.background
-            || invocable.use_explorer
-            || invocable.use_bash)
-            && !invocable.command.is_empty()
-        {
-            let command: &String = &wsl_path_or_self(
-                &invocable
-                    .command
-                    .replace("$pf64", &pf64)
-                    .replace("$pf86", &pf86)
-                    .replace("$userpath", &userpath)
-                    .replace("$syslive", "\\\\live.sysinternals.com\\tools\\"),
-                invocable.use_bash,
-            );
+        if !invocable.mode.is_direct() && !invocable.command.is_empty() {
+            let command: &String = &wsl_path_or_self(&tokens.replace(&invocable.command), use_bash);
             torun.arg(command);
             command_line.push_str(command);
             command_line.push(' ');
@@ -158,9 +119,9 @@ impl Invoker {
         // bash.exe -c wslpath -u C:/temp does not work, but bash.exe -c "wslpath -u C:/temp" does
         // add arguments from command configuration to command line
         for arg in invocable.arguments.iter() {
-            let param = &wsl_path_or_self(arg, invocable.use_bash);
+            let param = &wsl_path_or_self(arg, use_bash);
 
-            if invocable.use_bash {
+            if use_bash {
                 bash_command = format!("{0}{1} ", bash_command, param); //TOOD: quote?
             } else {
                 torun.arg(param);
@@ -171,9 +132,9 @@ impl Invoker {
 
         // append args from called command line to command line
         for arg in args.iter() {
-            let param: &String = &wsl_path_or_self(arg, invocable.use_bash);
+            let param: &String = &wsl_path_or_self(arg, use_bash);
 
-            if invocable.use_bash {
+            if use_bash {
                 bash_command = format!("{0}{1} ", bash_command, param); //TOOD: quote?
             } else {
                 torun.arg(param);
@@ -188,30 +149,7 @@ impl Invoker {
             torun.arg(bash_command);
         }
 
-        if verbose {
-            println!("{}", command_line);
-        }
-
-        if !dry_run {
-            if invocable.background {
-                let _discard = torun.status();
-            } else {
-                let results = torun.output().expect("failed to execute process");
-                let err = String::from_utf8_lossy(&results.stderr);
-
-                if !err.is_empty() {
-                    eprintln!("{}", err);
-                }
-
-                let out = String::from_utf8_lossy(&results.stdout);
-
-                if !out.is_empty() {
-                    println!("{}", out);
-                }
-            }
-        }
-
-        command_line
+        (torun, command_line)
     }
 }
 
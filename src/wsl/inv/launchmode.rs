@@ -0,0 +1,442 @@
+//! A LaunchMode determines how wink invokes the command in an Invocable:
+//! directly, through cmd.exe, through explorer.exe, or through bash.exe.
+//! Configuration files specify the mode by name, such as "mode": "cmd",
+//! or by name with options, such as "mode": { "cmd": { "wait": false } } or "mode": { "cmd_start": { "slash_c": false } }.
+
+/// The names of the launch modes in configuration files.
+pub const LAUNCH_MODE_NAMES: [&str; 7] = [
+    "direct",
+    "cmd",
+    "cmd_start",
+    "cmd_start_background",
+    "cmd_call",
+    "explorer",
+    "bash",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LaunchMode {
+    /// command [arguments]
+    #[default]
+    Direct,
+
+    /// cmd.exe [/wait] /c command [arguments]
+    Cmd { wait: bool },
+
+    /// cmd.exe [/wait] [/c] start command [arguments]
+    CmdStart { wait: bool, slash_c: bool },
+
+    /// cmd.exe [/c] start /b command [arguments], which returns without waiting for the command.
+    CmdStartBackground { slash_c: bool },
+
+    /// cmd.exe [/wait] /c call command [arguments]
+    CmdCall { wait: bool },
+
+    /// explorer.exe command [arguments]
+    Explorer,
+
+    /// bash.exe -c "command [arguments]"
+    Bash,
+}
+
+/// Options for the launch modes that accept them.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+struct LaunchOptions {
+    /// For modes that use cmd.exe, false to omit /wait. Defaults to true.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    wait: Option<bool>,
+
+    /// For modes that use start, whether to pass /c to cmd.exe. Defaults to true, except for cmd_start_background.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    slash_c: Option<bool>,
+}
+
+impl LaunchMode {
+    /// Return the name of this launch mode in configuration files.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LaunchMode::Direct => LAUNCH_MODE_NAMES[0],
+            LaunchMode::Cmd { .. } => LAUNCH_MODE_NAMES[1],
+            LaunchMode::CmdStart { .. } => LAUNCH_MODE_NAMES[2],
+            LaunchMode::CmdStartBackground { .. } => LAUNCH_MODE_NAMES[3],
+            LaunchMode::CmdCall { .. } => LAUNCH_MODE_NAMES[4],
+            LaunchMode::Explorer => LAUNCH_MODE_NAMES[5],
+            LaunchMode::Bash => LAUNCH_MODE_NAMES[6],
+        }
+    }
+
+    /// Return the launch mode with the given name and default options, if any.
+    pub fn from_name(name: &str) -> Option<LaunchMode> {
+        match name {
+            "direct" => Some(LaunchMode::Direct),
+            "cmd" => Some(LaunchMode::Cmd { wait: true }),
+            "cmd_start" => Some(LaunchMode::CmdStart { wait: true, slash_c: true }),
+            "cmd_start_background" => Some(LaunchMode::CmdStartBackground { slash_c: false }),
+            "cmd_call" => Some(LaunchMode::CmdCall { wait: true }),
+            "explorer" => Some(LaunchMode::Explorer),
+            "bash" => Some(LaunchMode::Bash),
+            _ => None,
+        }
+    }
+
+    /// Return the launch mode for the use_cmd, use_start, background, use_call, use_explorer, and use_bash
+    /// flags of earlier configuration files, or the names of the flags that conflict.
+    /// The mode passes the same arguments to cmd.exe as earlier versions of wink, such as start without /c for use_start,
+    /// except that use_call without use_cmd, which passed call to the command itself, uses cmd.exe /wait /c call.
+    pub fn from_flags(
+        use_cmd: bool,
+        use_start: bool,
+        background: bool,
+        use_call: bool,
+        use_explorer: bool,
+        use_bash: bool,
+    ) -> Result<LaunchMode, String> {
+        match (use_cmd, use_start, background, use_call, use_explorer, use_bash) {
+            (false, false, false, false, false, false) => Ok(LaunchMode::Direct),
+            (true, false, false, false, false, false) => Ok(LaunchMode::Cmd { wait: true }),
+            (use_cmd, true, false, false, false, false) => Ok(LaunchMode::CmdStart { wait: use_cmd, slash_c: use_cmd }),
+            (use_cmd, _, true, false, false, false) => Ok(LaunchMode::CmdStartBackground { slash_c: use_cmd }),
+            (_, false, false, true, false, false) => Ok(LaunchMode::CmdCall { wait: true }),
+            (false, false, false, false, true, false) => Ok(LaunchMode::Explorer),
+            (false, false, false, false, false, true) => Ok(LaunchMode::Bash),
+            _ => {
+                let flags = [
+                    ("use_cmd", use_cmd),
+                    ("use_start", use_start),
+                    ("background", background),
+                    ("use_call", use_call),
+                    ("use_explorer", use_explorer),
+                    ("use_bash", use_bash),
+                ];
+                let names: Vec<&str> = flags.iter().filter(|(_n, v)| *v).map(|(n, _v)| *n).collect();
+                Err(format!("conflicting launch flags: {0}", names.join(", ")))
+            }
+        }
+    }
+
+    /// Return true for modes that invoke the command without cmd.exe, explorer.exe, or bash.exe.
+    pub fn is_direct(&self) -> bool {
+        *self == LaunchMode::Direct
+    }
+
+    /// Return true for modes that use cmd.exe.
+    pub fn uses_cmd(&self) -> bool {
+        matches!(
+            self,
+            LaunchMode::Cmd { .. }
+                | LaunchMode::CmdStart { .. }
+                | LaunchMode::CmdStartBackground { .. }
+                | LaunchMode::CmdCall { .. }
+        )
+    }
+
+    /// Return the wait option for modes that accept it.
+    fn wait(&self) -> Option<bool> {
+        match self {
+            LaunchMode::Cmd { wait } | LaunchMode::CmdStart { wait, .. } | LaunchMode::CmdCall { wait } => Some(*wait),
+            _ => None,
+        }
+    }
+
+    /// Return the slash_c option for modes that accept it.
+    fn slash_c(&self) -> Option<bool> {
+        match self {
+            LaunchMode::CmdStart { slash_c, .. } | LaunchMode::CmdStartBackground { slash_c } => Some(*slash_c),
+            _ => None,
+        }
+    }
+
+    /// Return the options of this mode that differ from the defaults for its name.
+    fn options(&self) -> LaunchOptions {
+        let defaults = LaunchMode::from_name(self.name()).unwrap_or_default();
+
+        LaunchOptions {
+            wait: self.wait().filter(|&wait| Some(wait) != defaults.wait()),
+            slash_c: self.slash_c().filter(|&slash_c| Some(slash_c) != defaults.slash_c()),
+        }
+    }
+
+    /// Return the program that this mode invokes, or None to invoke the command directly.
+    pub fn program(&self) -> Option<&'static str> {
+        match self {
+            LaunchMode::Direct => None,
+            LaunchMode::Explorer => Some("explorer.exe"),
+            LaunchMode::Bash => Some("bash.exe"),
+            _ => Some("cmd.exe"),
+        }
+    }
+
+    /// Return the arguments that this mode passes to its program before the command.
+    pub fn program_arguments(&self) -> Vec<&'static str> {
+        let mut result: Vec<&'static str> = vec![];
+
+        if self.wait() == Some(true) {
+            result.push("/wait");
+        }
+
+        match self {
+            LaunchMode::Cmd { .. } => result.push("/c"),
+            LaunchMode::CmdStart { slash_c, .. } => {
+                if *slash_c {
+                    result.push("/c");
+                }
+
+                result.push("start");
+            }
+            LaunchMode::CmdStartBackground { slash_c } => {
+                if *slash_c {
+                    result.push("/c");
+                }
+
+                result.extend(["start", "/b"]);
+            }
+            LaunchMode::CmdCall { .. } => result.extend(["/c", "call"]),
+            LaunchMode::Bash => result.push("-c"),
+            LaunchMode::Direct | LaunchMode::Explorer => {}
+        }
+
+        result
+    }
+}
+
+impl std::fmt::Display for LaunchMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())?;
+
+        let options = self.options();
+
+        if options.wait == Some(false) {
+            write!(f, " (no wait)")?;
+        }
+
+        match options.slash_c {
+            Some(true) => write!(f, " (/c)")?,
+            Some(false) => write!(f, " (no /c)")?,
+            None => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Serialize as the name alone unless the options differ from their defaults.
+impl serde::Serialize for LaunchMode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let options = self.options();
+
+        if options.wait.is_some() || options.slash_c.is_some() {
+            let mut map = serializer.serialize_map(Some(1))?;
+            map.serialize_entry(self.name(), &options)?;
+            map.end()
+        } else {
+            serializer.serialize_str(self.name())
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for LaunchMode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(LaunchModeVisitor)
+    }
+}
+
+struct LaunchModeVisitor;
+
+impl<'de> serde::de::Visitor<'de> for LaunchModeVisitor {
+    type Value = LaunchMode;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "a launch mode name or a map from a launch mode name to its options")
+    }
+
+    fn visit_str<E>(self, name: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        LaunchMode::from_name(name).ok_or_else(|| E::unknown_variant(name, &LAUNCH_MODE_NAMES))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        use serde::de::Error;
+
+        let name: String = map
+            .next_key()?
+            .ok_or_else(|| A::Error::invalid_length(0, &"one launch mode"))?;
+        let mut mode = LaunchMode::from_name(&name)
+            .ok_or_else(|| A::Error::unknown_variant(&name, &LAUNCH_MODE_NAMES))?;
+        let options: LaunchOptions = map.next_value()?;
+
+        if let Some(wait) = options.wait {
+            match &mut mode {
+                LaunchMode::Cmd { wait: w } | LaunchMode::CmdStart { wait: w, .. } | LaunchMode::CmdCall { wait: w } => *w = wait,
+                _ => return Err(A::Error::custom(format!("launch mode {0} does not accept wait", name))),
+            }
+        }
+
+        if let Some(slash_c) = options.slash_c {
+            match &mut mode {
+                LaunchMode::CmdStart { slash_c: c, .. } | LaunchMode::CmdStartBackground { slash_c: c } => *c = slash_c,
+                _ => return Err(A::Error::custom(format!("launch mode {0} does not accept slash_c", name))),
+            }
+        }
+
+        if map.next_key::<String>()?.is_some() {
+            return Err(A::Error::invalid_length(2, &"one launch mode"));
+        }
+
+        Ok(mode)
+    }
+}
+
+/// Describe the name and map forms of launch modes for the JSON Schema.
+impl schemars::JsonSchema for LaunchMode {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "LaunchMode".into()
+    }
+
+    fn json_schema(_generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let wait = schemars::json_schema!({ "type": "boolean", "description": "False to omit /wait for cmd.exe. Defaults to true." });
+        let slash_c = schemars::json_schema!({
+            "type": "boolean",
+            "description": "Whether to pass /c to cmd.exe before start. Defaults to true, except for cmd_start_background."
+        });
+        let options = |properties: serde_json::Value| {
+            schemars::json_schema!({ "type": "object", "properties": properties, "additionalProperties": false })
+        };
+        schemars::json_schema!({
+            "description": "How to invoke the command: direct (the default), cmd, cmd_start, cmd_start_background, cmd_call, explorer, or bash, or a map from a mode that uses cmd.exe to options.",
+            "oneOf": [
+                { "type": "string", "enum": LAUNCH_MODE_NAMES },
+                {
+                    "type": "object",
+                    "properties": {
+                        "cmd": options(serde_json::json!({ "wait": wait })),
+                        "cmd_start": options(serde_json::json!({ "wait": wait, "slash_c": slash_c })),
+                        "cmd_start_background": options(serde_json::json!({ "slash_c": slash_c })),
+                        "cmd_call": options(serde_json::json!({ "wait": wait }))
+                    },
+                    "minProperties": 1,
+                    "maxProperties": 1,
+                    "additionalProperties": false
+                }
+            ]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wsl::inv::launchmode::LaunchMode;
+
+    #[test]
+    fn it_migrates_flags() {
+        assert_eq!(LaunchMode::from_flags(false, false, false, false, false, false), Ok(LaunchMode::Direct));
+        assert_eq!(LaunchMode::from_flags(true, false, false, false, false, false), Ok(LaunchMode::Cmd { wait: true }));
+        assert_eq!(LaunchMode::from_flags(false, false, true, false, false, false), Ok(LaunchMode::CmdStartBackground { slash_c: false }));
+        assert_eq!(LaunchMode::from_flags(false, false, false, false, true, false), Ok(LaunchMode::Explorer));
+        assert_eq!(
+            LaunchMode::from_flags(false, false, false, false, true, true),
+            Err("conflicting launch flags: use_explorer, use_bash".to_string())
+        );
+    }
+
+    /// Return the program and the arguments before the command c that earlier versions of wink invoked for the launch flags.
+    fn legacy_argv(use_cmd: bool, use_start: bool, background: bool, use_call: bool, use_explorer: bool, use_bash: bool) -> Vec<&'static str> {
+        let mut argv = vec![match (use_cmd || use_start || background, use_bash, use_explorer) {
+            (true, _, _) => "cmd.exe",
+            (false, true, _) => "bash.exe",
+            (false, false, true) => "explorer.exe",
+            (false, false, false) => "c",
+        }];
+
+        if use_cmd {
+            if !background {
+                argv.push("/wait");
+            }
+
+            argv.push("/c");
+        }
+
+        if use_bash {
+            argv.push("-c");
+        }
+
+        if use_start || background {
+            argv.push("start");
+        }
+
+        if background {
+            argv.push("/b");
+        }
+
+        if use_call {
+            argv.push("call");
+        }
+
+        if use_cmd || use_start || background || use_explorer || use_bash {
+            argv.push("c");
+        }
+
+        argv
+    }
+
+    #[test]
+    fn it_keeps_the_command_lines_of_flags() {
+        for bits in 0..64 {
+            let flag = |n: u32| bits & (1 << n) != 0;
+            let flags = (flag(0), flag(1), flag(2), flag(3), flag(4), flag(5));
+            let (use_cmd, use_start, background, use_call, use_explorer, use_bash) = flags;
+            let count = (0..6).filter(|n| flag(*n)).count();
+            let conflicting = ((use_explorer || use_bash) && count > 1) || (use_call && (use_start || background));
+
+            match LaunchMode::from_flags(use_cmd, use_start, background, use_call, use_explorer, use_bash) {
+                Err(_e) => assert!(conflicting, "{:?}", flags),
+                // earlier versions passed call to the command instead of cmd.exe
+                Ok(mode) if use_call && !use_cmd => assert_eq!(mode, LaunchMode::CmdCall { wait: true }),
+                Ok(mode) => {
+                    assert!(!conflicting, "{:?}", flags);
+                    let mut argv = vec![mode.program().unwrap_or("c")];
+                    argv.extend(mode.program_arguments());
+
+                    if !mode.is_direct() {
+                        argv.push("c");
+                    }
+
+                    assert_eq!(argv, legacy_argv(use_cmd, use_start, background, use_call, use_explorer, use_bash), "{:?}", flags);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_round_trips_options() {
+        for json in [
+            "\"bash\"",
+            "\"cmd\"",
+            "{\"cmd\":{\"wait\":false}}",
+            "{\"cmd_call\":{\"wait\":false}}",
+            "{\"cmd_start\":{\"wait\":false,\"slash_c\":false}}",
+            "{\"cmd_start_background\":{\"slash_c\":true}}",
+        ] {
+            let mode: LaunchMode = serde_json::from_str(json).unwrap();
+            assert_eq!(serde_json::to_string(&mode).unwrap(), json);
+        }
+
+        assert!(serde_json::from_str::<LaunchMode>("{\"bash\":{\"wait\":false}}").is_err());
+        assert!(serde_json::from_str::<LaunchMode>("{\"cmd\":{\"slash_c\":false}}").is_err());
+        assert_eq!(LaunchMode::CmdStartBackground { slash_c: true }.to_string(), "cmd_start_background (/c)");
+        assert!(serde_json::from_str::<LaunchMode>("\"shell\"").is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! Tokens that wink replaces in the command of an Invocable,
+//! such as $pf64 for the 64-bit Program Files directory.
+
+/// The names and descriptions of the tokens that wink replaces in commands.
+pub const TOKENS: [(&str, &str); 4] = [
+    ("$pf64", "the 64-bit Program Files directory, such as C:\\Program Files"),
+    ("$pf86", "the 32-bit Program Files directory, such as C:\\Program Files (x86)"),
+    ("$userpath", "the Windows user profile directory, such as C:\\Users\\jw"),
+    ("$syslive", "the Sysinternals Live share, \\\\live.sysinternals.com\\tools\\"),
+];
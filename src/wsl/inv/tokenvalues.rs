@@ -0,0 +1,59 @@
+//! TokenValues contains the values that wink substitutes for the tokens in the command of an Invocable,
+//! such as the path to the 64-bit Program Files directory for $pf64.
+
+use crate::wsl::wsl_path_or_self;
+
+/// The Sysinternals Live share that replaces $syslive.
+pub const SYSLIVE: &str = "\\\\live.sysinternals.com\\tools\\";
+
+#[derive(Debug, Clone, Default)]
+pub struct TokenValues {
+    /// The value of $pf64, or empty if wink could not determine it.
+    pub pf64: String,
+
+    /// The value of $pf86, or empty if wink could not determine it.
+    pub pf86: String,
+
+    /// The value of $userpath, or empty if wink could not determine it.
+    pub userpath: String,
+}
+
+impl TokenValues {
+    /// Return the values of the tokens, which wink gets from Windows environment variables through cmd.exe.
+    /// Tokens that wink cannot determine, such as when cmd.exe is not available, are empty.
+    pub fn get() -> TokenValues {
+        TokenValues {
+            pf64: TokenValues::windows_variable("%ProgramFiles%"),
+            pf86: TokenValues::windows_variable("%ProgramFiles(x86)%"),
+            userpath: TokenValues::windows_variable("%USERPROFILE%"),
+        }
+    }
+
+    /// Return the value of the given Windows environment variable as a path, or empty if unavailable.
+    fn windows_variable(variable: &str) -> String {
+        match std::process::Command::new("cmd.exe").arg("/c").arg("echo").arg(variable).output() {
+            Ok(results) if results.status.code() == Some(0) => {
+                wsl_path_or_self(String::from_utf8_lossy(&results.stdout).trim(), false)
+            }
+            _ => String::new(),
+        }
+    }
+
+    /// Return the given command with the tokens replaced by their values.
+    pub fn replace(&self, command: &str) -> String {
+        command
+            .replace("$pf64", &self.pf64)
+            .replace("$pf86", &self.pf86)
+            .replace("$userpath", &self.userpath)
+            .replace("$syslive", SYSLIVE)
+    }
+
+    /// Return the tokens in the given command that have no value, such as $pf64 without cmd.exe.
+    pub fn unresolved(&self, command: &str) -> Vec<&'static str> {
+        [("$pf64", &self.pf64), ("$pf86", &self.pf86), ("$userpath", &self.userpath)]
+            .iter()
+            .filter(|(token, value)| value.is_empty() && command.contains(token))
+            .map(|(token, _value)| *token)
+            .collect()
+    }
+}